@@ -1,22 +1,25 @@
-use std::{fs, path::PathBuf};
-use std::path::Path;
+use std::fs;
+
+use divan::{black_box, Bencher};
 
 use aoc2023::day01::{part1, part2};
+use aoc2023::registry::input_path;
+
+const DAY: u32 = 1;
 
 fn main() {
     divan::main();
 }
 
+// Works straight on the input text, so there is no separate parsing step
 #[divan::bench]
-fn part1_bench() {
-    let filename: PathBuf = Path::new("data").join("day01-input.txt");
-    let input_string = fs::read_to_string(filename).unwrap();
-    part1(divan::black_box(input_string.as_str()));
+fn part1_bench(bencher: Bencher) {
+    let input = fs::read_to_string(input_path(DAY)).unwrap();
+    bencher.bench(|| part1(black_box(&input)));
 }
 
 #[divan::bench]
-fn part2_bench() {
-    let filename: PathBuf = Path::new("data").join("day02-input.txt");
-    let input_string = fs::read_to_string(filename).unwrap();
-    part2(divan::black_box(input_string.as_str()));
+fn part2_bench(bencher: Bencher) {
+    let input = fs::read_to_string(input_path(DAY)).unwrap();
+    bencher.bench(|| part2(black_box(&input)));
 }
@@ -1,30 +1,33 @@
-use std::{fs, path::PathBuf};
-use std::path::Path;
+use std::fs;
 
-use aoc2023::day02::{part1, part2, CubeSet};
+use divan::{black_box, Bencher};
+
+use aoc2023::day02::{get_sum_of_power, get_total_games, parse_games, CubeSet};
+use aoc2023::registry::input_path;
+
+const DAY: u32 = 2;
 
 fn main() {
     divan::main();
 }
 
 #[divan::bench]
-fn part1_bench() {
-    let filename: PathBuf = Path::new("data").join("day02-input.txt");
-    let input_string = fs::read_to_string(filename).unwrap();
-    let cube_set = CubeSet {
-        red: 12,
-        green: 13,
-        blue: 14,
-    };
-    part1(
-        divan::black_box(input_string.as_str()),
-        divan::black_box(cube_set),
-    );
+fn parse_bench(bencher: Bencher) {
+    let input = fs::read_to_string(input_path(DAY)).unwrap();
+    bencher.bench(|| parse_games(black_box(&input)));
+}
+
+#[divan::bench]
+fn part1_bench(bencher: Bencher) {
+    let input = fs::read_to_string(input_path(DAY)).unwrap();
+    let games = parse_games(&input).unwrap();
+    let limits = CubeSet::rgb(12, 13, 14);
+    bencher.bench(|| get_total_games(black_box(&games), black_box(&limits)));
 }
 
 #[divan::bench]
-fn part2_bench() {
-    let filename: PathBuf = Path::new("data").join("day02-input.txt");
-    let input_string = fs::read_to_string(filename).unwrap();
-    part2(divan::black_box(input_string.as_str()));
+fn part2_bench(bencher: Bencher) {
+    let input = fs::read_to_string(input_path(DAY)).unwrap();
+    let games = parse_games(&input).unwrap();
+    bencher.bench(|| get_sum_of_power(black_box(&games)));
 }
@@ -1,22 +1,33 @@
-use std::{fs, path::PathBuf};
-use std::path::Path;
+use std::fs;
 
-use aoc2023::day04::{part1, part2};
+use divan::{black_box, Bencher};
+use num::BigUint;
+
+use aoc2023::day04::{compute_winning_points, count_total_cards, parse_cards};
+use aoc2023::registry::input_path;
+
+const DAY: u32 = 4;
 
 fn main() {
     divan::main();
 }
 
 #[divan::bench]
-fn part1_bench() {
-    let filename: PathBuf = Path::new("data").join("day04-input.txt");
-    let input_string = fs::read_to_string(filename).unwrap();
-    part1(divan::black_box(input_string.as_str()));
+fn parse_bench(bencher: Bencher) {
+    let input = fs::read_to_string(input_path(DAY)).unwrap();
+    bencher.bench(|| parse_cards(black_box(&input)));
+}
+
+#[divan::bench]
+fn part1_bench(bencher: Bencher) {
+    let input = fs::read_to_string(input_path(DAY)).unwrap();
+    let cards = parse_cards(&input).unwrap();
+    bencher.bench(|| compute_winning_points(black_box(&cards)));
 }
 
 #[divan::bench]
-fn part2_bench() {
-    let filename: PathBuf = Path::new("data").join("day04-input.txt");
-    let input_string = fs::read_to_string(filename).unwrap();
-    part2(divan::black_box(input_string.as_str()));
+fn part2_bench(bencher: Bencher) {
+    let input = fs::read_to_string(input_path(DAY)).unwrap();
+    let cards = parse_cards(&input).unwrap();
+    bencher.bench(|| count_total_cards::<BigUint>(black_box(&cards)));
 }
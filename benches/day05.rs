@@ -1,15 +1,32 @@
-use std::{fs, path::PathBuf};
-use std::path::Path;
+use std::fs;
 
-use aoc2023::day05::part1;
+use divan::{black_box, Bencher};
+
+use aoc2023::day05::{find_closest_location, find_closest_location_reversed, parse_input};
+use aoc2023::registry::input_path;
+
+const DAY: u32 = 5;
 
 fn main() {
     divan::main();
 }
 
 #[divan::bench]
-fn part1_bench() {
-    let filename: PathBuf = Path::new("data").join("day05-input.txt");
-    let input_string = fs::read_to_string(filename).unwrap();
-    part1(divan::black_box(input_string.as_str()));
+fn parse_bench(bencher: Bencher) {
+    let input = fs::read_to_string(input_path(DAY)).unwrap();
+    bencher.bench(|| parse_input(black_box(&input)));
+}
+
+#[divan::bench]
+fn part1_bench(bencher: Bencher) {
+    let input = fs::read_to_string(input_path(DAY)).unwrap();
+    let almanac = parse_input(&input);
+    bencher.bench(|| find_closest_location(black_box(&almanac)));
+}
+
+#[divan::bench]
+fn part2_bench(bencher: Bencher) {
+    let input = fs::read_to_string(input_path(DAY)).unwrap();
+    let almanac = parse_input(&input);
+    bencher.bench(|| find_closest_location_reversed(black_box(&almanac)));
 }
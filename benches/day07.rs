@@ -1,22 +1,32 @@
-use std::{fs, path::PathBuf};
-use std::path::Path;
+use std::fs;
 
-use aoc2023::day07::{part1, part2};
+use divan::{black_box, Bencher};
+
+use aoc2023::day07::{parse_hands, total_winnings, total_winnings_with_jokers};
+use aoc2023::registry::input_path;
+
+const DAY: u32 = 7;
 
 fn main() {
     divan::main();
 }
 
 #[divan::bench]
-fn part1_bench() {
-    let filename: PathBuf = Path::new("data").join("day07-input.txt");
-    let input_string = fs::read_to_string(filename).unwrap();
-    part1(divan::black_box(input_string.as_str()));
+fn parse_bench(bencher: Bencher) {
+    let input = fs::read_to_string(input_path(DAY)).unwrap();
+    bencher.bench(|| parse_hands(black_box(&input)));
+}
+
+#[divan::bench]
+fn part1_bench(bencher: Bencher) {
+    let input = fs::read_to_string(input_path(DAY)).unwrap();
+    let hands = parse_hands(&input);
+    bencher.bench(|| total_winnings(black_box(&hands)));
 }
 
 #[divan::bench]
-fn part2_bench() {
-    let filename: PathBuf = Path::new("data").join("day07-input.txt");
-    let input_string = fs::read_to_string(filename).unwrap();
-    part2(divan::black_box(input_string.as_str()));
+fn part2_bench(bencher: Bencher) {
+    let input = fs::read_to_string(input_path(DAY)).unwrap();
+    let hands = parse_hands(&input);
+    bencher.bench(|| total_winnings_with_jokers(black_box(&hands)));
 }
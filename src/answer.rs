@@ -0,0 +1,212 @@
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+use num::{BigInt, BigUint};
+use serde::{Serialize, Serializer};
+
+use crate::error::AocError;
+
+// The answer to a part of any day. Each day returns the widest type it needs
+// and converts into this, so nothing is truncated on the way to the runner.
+// Numbers compare by value across variants: 42 as i64 equals 42 as u128.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    I64(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
+    BigInt(BigInt),
+    Str(String),
+}
+
+impl Answer {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Answer::I64(_) => "i64",
+            Answer::U64(_) => "u64",
+            Answer::I128(_) => "i128",
+            Answer::U128(_) => "u128",
+            Answer::BigInt(_) => "bigint",
+            Answer::Str(_) => "string",
+        }
+    }
+
+    // Whether a recorded answer, e.g. from answers.toml, is this one
+    pub fn matches(&self, expected: &str) -> bool {
+        expected.parse::<Answer>().is_ok_and(|expected| &expected == self)
+    }
+
+    pub fn to_bigint(&self) -> Option<BigInt> {
+        match self {
+            Answer::I64(n) => Some(BigInt::from(*n)),
+            Answer::U64(n) => Some(BigInt::from(*n)),
+            Answer::I128(n) => Some(BigInt::from(*n)),
+            Answer::U128(n) => Some(BigInt::from(*n)),
+            Answer::BigInt(n) => Some(n.clone()),
+            Answer::Str(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::I64(n) => write!(f, "{}", n),
+            Answer::U64(n) => write!(f, "{}", n),
+            Answer::I128(n) => write!(f, "{}", n),
+            Answer::U128(n) => write!(f, "{}", n),
+            Answer::BigInt(n) => write!(f, "{}", n),
+            Answer::Str(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        match (self, other) {
+            (Answer::Str(a), Answer::Str(b)) => a == b,
+            (Answer::Str(_), _) | (_, Answer::Str(_)) => false,
+            _ => self.to_bigint() == other.to_bigint(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+// Integers get the narrowest variant that holds them, anything else is kept
+// as a string
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Answer, Infallible> {
+        let s = s.trim();
+        let answer = if let Ok(n) = s.parse::<i64>() {
+            Answer::I64(n)
+        } else if let Ok(n) = s.parse::<u64>() {
+            Answer::U64(n)
+        } else if let Ok(n) = s.parse::<i128>() {
+            Answer::I128(n)
+        } else if let Ok(n) = s.parse::<u128>() {
+            Answer::U128(n)
+        } else if let Ok(n) = s.parse::<BigInt>() {
+            Answer::BigInt(n)
+        } else {
+            Answer::Str(s.to_string())
+        };
+        Ok(answer)
+    }
+}
+
+// Always written as a string, JSON numbers lose precision past 2^53
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident, $wide:ty, $($t:ty),+) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::$variant(n as $wide)
+                }
+            }
+        )+
+    };
+}
+
+answer_from!(I64, i64, i8, i16, i32, i64, isize);
+answer_from!(U64, u64, u8, u16, u32, u64, usize);
+answer_from!(I128, i128, i128);
+answer_from!(U128, u128, u128);
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Answer {
+        Answer::BigInt(n)
+    }
+}
+
+impl From<BigUint> for Answer {
+    fn from(n: BigUint) -> Answer {
+        Answer::BigInt(BigInt::from(n))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Str(s.to_string())
+    }
+}
+
+// What a part returns, either an answer or an answer that can fail, e.g. when
+// overflow checks are on
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<Answer, AocError>;
+}
+
+impl<T: Into<Answer>> IntoAnswer for T {
+    fn into_answer(self) -> Result<Answer, AocError> {
+        Ok(self.into())
+    }
+}
+
+impl<T: Into<Answer>> IntoAnswer for Result<T, AocError> {
+    fn into_answer(self) -> Result<Answer, AocError> {
+        self.map(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Answer {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_answer() {
+        assert!(matches!(parse("-12"), Answer::I64(-12)));
+        assert!(matches!(parse(" 18446744073709551615\n"), Answer::U64(u64::MAX)));
+        assert!(matches!(parse("-18446744073709551616"), Answer::I128(_)));
+        assert!(matches!(parse("340282366920938463463374607431768211455"), Answer::U128(u128::MAX)));
+        assert!(matches!(parse("340282366920938463463374607431768211456"), Answer::BigInt(_)));
+        assert!(matches!(parse("EBZUR"), Answer::Str(_)));
+    }
+
+    #[test]
+    fn test_answer_equality() {
+        assert_eq!(Answer::from(42u32), Answer::from(42i128));
+        assert_eq!(Answer::from(BigUint::from(7u8)), parse("7"));
+        assert_ne!(Answer::from(-1i32), Answer::from(u64::MAX));
+        assert_ne!(Answer::from("42"), Answer::from(42u64));
+        assert_eq!(Answer::from("abc"), parse("abc"));
+        assert!(Answer::from(6189740u64).matches("6189740"));
+        assert!(!Answer::from(6189740u64).matches("6189741"));
+    }
+
+    #[test]
+    fn test_into_answer() {
+        assert_eq!(7u32.into_answer(), Ok(Answer::from(7u64)));
+        assert_eq!(Ok::<u64, AocError>(7).into_answer(), Ok(Answer::from(7u64)));
+        let overflow = AocError::Overflow { context: "test".to_string() };
+        assert_eq!(Err::<u64, AocError>(overflow.clone()).into_answer(), Err(overflow));
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let big = BigUint::from(2u8).pow(200);
+        for answer in [Answer::from(-5i64), Answer::from(u128::MAX), Answer::from(big), Answer::from("x y")] {
+            assert_eq!(parse(&answer.to_string()), answer);
+        }
+        assert_eq!(serde_json::to_string(&Answer::from(u128::MAX)).unwrap(), "\"340282366920938463463374607431768211455\"");
+    }
+}
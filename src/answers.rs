@@ -0,0 +1,382 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::registry::{input_path, Solution};
+use crate::runner::csv_field;
+
+// Recorded answers keyed by day and part, read from a file like:
+//
+// [day01]
+// part1 = 54630
+// part2 = "54770"
+//
+// Answers can be written as integers or strings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub days: BTreeMap<u32, BTreeMap<u32, String>>,
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    UnknownKey(String),
+    UnsupportedValue { key: String, value: String },
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnswersError::Io(err) => write!(f, "could not read answers: {}", err),
+            AnswersError::Toml(err) => write!(f, "could not parse answers: {}", err),
+            AnswersError::UnknownKey(key) => write!(f, "unknown key {}, expected dayNN.partN", key),
+            AnswersError::UnsupportedValue { key, value } => {
+                write!(f, "{}: answer {} should be an integer or a string", key, value)
+            },
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers, AnswersError> {
+        let content = fs::read_to_string(path).map_err(AnswersError::Io)?;
+        Answers::parse(content.as_str())
+    }
+
+    pub fn parse(input: &str) -> Result<Answers, AnswersError> {
+        let table: toml::Table = input.parse().map_err(AnswersError::Toml)?;
+        let mut answers = Answers::default();
+
+        for (day_key, parts) in table.iter() {
+            let day = parse_key(day_key, "day").ok_or_else(|| AnswersError::UnknownKey(day_key.clone()))?;
+            let parts = parts.as_table().ok_or_else(|| AnswersError::UnknownKey(day_key.clone()))?;
+
+            for (part_key, value) in parts.iter() {
+                let key = format!("{}.{}", day_key, part_key);
+                let part = parse_key(part_key, "part").ok_or_else(|| AnswersError::UnknownKey(key.clone()))?;
+                let answer = answer_value(value)
+                    .ok_or_else(|| AnswersError::UnsupportedValue { key, value: value.to_string() })?;
+                answers.insert(day, part, answer);
+            }
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.days.get(&day).and_then(|parts| parts.get(&part)).map(|answer| answer.as_str())
+    }
+
+    pub fn insert(&mut self, day: u32, part: u32, answer: String) {
+        self.days.entry(day).or_default().insert(part, answer);
+    }
+}
+
+pub(crate) fn parse_key(key: &str, prefix: &str) -> Option<u32> {
+    key.strip_prefix(prefix).and_then(|n| n.parse::<u32>().ok())
+}
+
+pub(crate) fn answer_value(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::Integer(n) => Some(n.to_string()),
+        toml::Value::String(s) => Some(s.trim().to_string()),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Correct,
+    Mismatch { expected: String },
+    Missing,
+    Panicked(String),
+    Failed(String),
+    NoInput,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Correct => write!(f, "ok"),
+            Status::Mismatch { .. } => write!(f, "MISMATCH"),
+            Status::Missing => write!(f, "missing"),
+            Status::Panicked(_) => write!(f, "PANIC"),
+            Status::Failed(_) => write!(f, "ERROR"),
+            Status::NoInput => write!(f, "no input"),
+        }
+    }
+}
+
+impl Status {
+    // Stable name for machine-readable output
+    pub fn key(&self) -> &'static str {
+        match self {
+            Status::Correct => "ok",
+            Status::Mismatch { .. } => "mismatch",
+            Status::Missing => "missing",
+            Status::Panicked(_) => "panicked",
+            Status::Failed(_) => "failed",
+            Status::NoInput => "no_input",
+        }
+    }
+
+    // What went wrong, for the statuses that carry more than their name
+    pub fn detail(&self) -> Option<String> {
+        match self {
+            Status::Mismatch { expected } => Some(format!("expected {}", expected)),
+            Status::Panicked(message) | Status::Failed(message) => Some(message.clone()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verification {
+    pub day: u32,
+    pub part: u32,
+    pub source: String,
+    pub actual: Option<Answer>,
+    pub status: Status,
+}
+
+impl Verification {
+    pub fn is_failure(&self) -> bool {
+        matches!(self.status, Status::Mismatch { .. } | Status::Panicked(_) | Status::Failed(_))
+    }
+}
+
+// Flat record with the status as its key and the expected answer, when there
+// is one, kept apart from the detail message
+impl Serialize for Verification {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let expected = match &self.status {
+            Status::Mismatch { expected } => Some(expected),
+            _ => None,
+        };
+        let mut verification = serializer.serialize_struct("Verification", 7)?;
+        verification.serialize_field("day", &self.day)?;
+        verification.serialize_field("part", &self.part)?;
+        verification.serialize_field("source", &self.source)?;
+        verification.serialize_field("status", self.status.key())?;
+        verification.serialize_field("answer", &self.actual)?;
+        verification.serialize_field("expected", &expected)?;
+        verification.serialize_field("detail", &self.status.detail())?;
+        verification.end()
+    }
+}
+
+// Runs every part of the given solutions against its puzzle input in data/
+pub fn verify(solutions: &[Solution], answers: &Answers) -> Vec<Verification> {
+    let mut verifications = Vec::new();
+
+    for solution in solutions.iter() {
+        let path = input_path(solution.day);
+        let source = path.display().to_string();
+        let input = fs::read_to_string(&path).ok();
+        for part in 1..=2 {
+            let expected = answers.get(solution.day, part);
+            let verification = match input.as_ref() {
+                None => Verification {
+                    day: solution.day,
+                    part,
+                    source: source.clone(),
+                    actual: None,
+                    status: Status::NoInput,
+                },
+                Some(input) => {
+                    let run = solution.part(part).expect("Solutions should have two parts.");
+                    verify_part(solution.day, part, source.clone(), || run(input.as_str()), expected)
+                },
+            };
+            verifications.push(verification);
+        }
+    }
+
+    verifications
+}
+
+// Expected answers are parsed, so "42" matches 42 whatever its integer type.
+// A part that returns an error, e.g. on overflow, fails without an answer.
+pub fn verify_part<F: FnOnce() -> Result<Answer, AocError>>(
+    day: u32,
+    part: u32,
+    source: String,
+    run: F,
+    expected: Option<&str>,
+) -> Verification {
+    let (actual, status) = match panic::catch_unwind(AssertUnwindSafe(run)) {
+        Err(payload) => (None, Status::Panicked(panic_message(payload.as_ref()))),
+        Ok(Err(err)) => (None, Status::Failed(err.to_string())),
+        Ok(Ok(actual)) => {
+            let status = match expected {
+                None => Status::Missing,
+                Some(expected) if actual.matches(expected) => Status::Correct,
+                Some(expected) => Status::Mismatch { expected: expected.to_string() },
+            };
+            (Some(actual), status)
+        },
+    };
+    Verification { day, part, source, actual, status }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+pub fn summary_table(verifications: &[Verification]) -> String {
+    let mut lines: Vec<String> = vec![
+        format!("{:>3} | {:>4} | {:<30} | {:<8} | {:>20} | {}", "Day", "Part", "Input", "Status", "Answer", "Detail"),
+    ];
+    for verification in verifications.iter() {
+        let detail = verification.status.detail().unwrap_or_default();
+        lines.push(format!(
+            "{:>3} | {:>4} | {:<30} | {:<8} | {:>20} | {}",
+            verification.day,
+            verification.part,
+            verification.source,
+            verification.status.to_string(),
+            verification.actual.as_ref().map(|actual| actual.to_string()).unwrap_or_else(|| "-".to_string()),
+            detail,
+        ).trim_end().to_string());
+    }
+
+    let count = |f: fn(&Status) -> bool| verifications.iter().filter(|v| f(&v.status)).count();
+    lines.push(format!(
+        "{} ok, {} mismatched, {} missing, {} panicked, {} failed, {} without input",
+        count(|s| matches!(s, Status::Correct)),
+        count(|s| matches!(s, Status::Mismatch { .. })),
+        count(|s| matches!(s, Status::Missing)),
+        count(|s| matches!(s, Status::Panicked(_))),
+        count(|s| matches!(s, Status::Failed(_))),
+        count(|s| matches!(s, Status::NoInput)),
+    ));
+    lines.join("\n")
+}
+
+pub fn verifications_json(verifications: &[Verification]) -> String {
+    serde_json::to_string_pretty(verifications).expect("Verifications should serialize.")
+}
+
+pub fn verifications_csv(verifications: &[Verification]) -> String {
+    let mut lines: Vec<String> = vec!["day,part,source,status,answer,expected,detail".to_string()];
+    for verification in verifications.iter() {
+        lines.push(verification_fields(verification).join(","));
+    }
+    lines.join("\n")
+}
+
+// The CSV columns shared with the batch output, in header order
+pub(crate) fn verification_fields(verification: &Verification) -> Vec<String> {
+    let expected = match &verification.status {
+        Status::Mismatch { expected } => expected.as_str(),
+        _ => "",
+    };
+    vec![
+        verification.day.to_string(),
+        verification.part.to_string(),
+        csv_field(&verification.source),
+        verification.status.key().to_string(),
+        csv_field(&verification.actual.as_ref().map(|actual| actual.to_string()).unwrap_or_default()),
+        csv_field(expected),
+        csv_field(&verification.status.detail().unwrap_or_default()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let input = "[day01]
+part1 = 142
+part2 = \"281\"
+
+[day12]
+part2 = 525152";
+
+        let answers = Answers::parse(input).unwrap();
+        assert_eq!(answers.get(1, 1), Some("142"));
+        assert_eq!(answers.get(1, 2), Some("281"));
+        assert_eq!(answers.get(12, 1), None);
+        assert_eq!(answers.get(12, 2), Some("525152"));
+
+        assert!(matches!(Answers::parse("[day01]\npart3x = 1"), Err(AnswersError::UnknownKey(_))));
+        assert!(matches!(Answers::parse("[dayone]\npart1 = 1"), Err(AnswersError::UnknownKey(_))));
+        assert!(matches!(Answers::parse("[day01]\npart1 = 1.5"), Err(AnswersError::UnsupportedValue { .. })));
+    }
+
+    #[test]
+    fn test_verify_part() {
+        let correct = verify_part(1, 1, "test".to_string(), || Ok(Answer::from(142u32)), Some("142"));
+        assert_eq!(correct.status, Status::Correct);
+
+        let mismatch = verify_part(1, 2, "test".to_string(), || Ok(Answer::from(280u64)), Some("281"));
+        assert_eq!(mismatch.status, Status::Mismatch { expected: "281".to_string() });
+        assert!(mismatch.is_failure());
+
+        let missing = verify_part(2, 1, "test".to_string(), || Ok(Answer::from(8i64)), None);
+        assert_eq!(missing.status, Status::Missing);
+        assert!(!missing.is_failure());
+
+        let panicked = verify_part(2, 2, "test".to_string(), || panic!("bad input"), Some("2286"));
+        assert_eq!(panicked.status, Status::Panicked("bad input".to_string()));
+        assert_eq!(panicked.actual, None);
+
+        let overflow = AocError::Overflow { context: "card copies".to_string() };
+        let failed = verify_part(4, 2, "test".to_string(), || Err(overflow), Some("5704953"));
+        assert_eq!(failed.status, Status::Failed("arithmetic overflow in card copies".to_string()));
+        assert!(failed.is_failure());
+
+        let table = summary_table(&[correct, mismatch, missing, panicked, failed]);
+        assert!(table.contains("expected 281"));
+        assert!(table.contains("arithmetic overflow in card copies"));
+        assert!(table.ends_with("1 ok, 1 mismatched, 1 missing, 1 panicked, 1 failed, 0 without input"));
+    }
+
+    #[test]
+    fn test_verifications_json_and_csv() {
+        let correct = verify_part(1, 1, "data/day01-input.txt".to_string(), || Ok(Answer::from(142u32)), Some("142"));
+        let mismatch = verify_part(1, 2, "a,b.txt".to_string(), || Ok(Answer::from(280u64)), Some("281"));
+        let panicked = verify_part(2, 1, "test".to_string(), || panic!("bad input"), None);
+        let verifications = vec![correct, mismatch, panicked];
+
+        let json: serde_json::Value = serde_json::from_str(&verifications_json(&verifications)).unwrap();
+        assert_eq!(json[0], serde_json::json!({
+            "day": 1,
+            "part": 1,
+            "source": "data/day01-input.txt",
+            "status": "ok",
+            "answer": "142",
+            "expected": null,
+            "detail": null,
+        }));
+        assert_eq!(json[1]["status"], "mismatch");
+        assert_eq!(json[1]["expected"], "281");
+        assert_eq!(json[2]["answer"], serde_json::Value::Null);
+        assert_eq!(json[2]["detail"], "bad input");
+
+        let csv = verifications_csv(&verifications);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "day,part,source,status,answer,expected,detail");
+        assert_eq!(lines[1], "1,1,data/day01-input.txt,ok,142,,");
+        assert_eq!(lines[2], "1,2,\"a,b.txt\",mismatch,280,281,expected 281");
+        assert_eq!(lines[3], "2,1,test,panicked,,,bad input");
+    }
+}
@@ -0,0 +1,224 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::answers::{verification_fields, verify_part, Status, Verification};
+use crate::registry::Solution;
+use crate::timing::{format_duration, nanos, serialize_optional_nanos};
+
+// One part of a solution run on one of the batch inputs. The timings are
+// missing when the input could not be read or the part panicked. Serialized
+// as the verification with the timings added, the file is its source.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BatchRun {
+    #[serde(skip)]
+    pub file: PathBuf,
+    #[serde(flatten)]
+    pub verification: Verification,
+    #[serde(rename = "parse_ns", serialize_with = "serialize_optional_nanos")]
+    pub parse: Option<Duration>,
+    #[serde(rename = "solve_ns", serialize_with = "serialize_optional_nanos")]
+    pub solve: Option<Duration>,
+}
+
+#[derive(Debug)]
+pub enum BatchError {
+    Io { file: PathBuf, err: std::io::Error },
+    Pattern(glob::PatternError),
+    NoInputs(String),
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BatchError::Io { file, err } => write!(f, "{}: {}", file.display(), err),
+            BatchError::Pattern(err) => write!(f, "invalid pattern: {}", err),
+            BatchError::NoInputs(pattern) => write!(f, "no inputs found for {}", pattern),
+        }
+    }
+}
+
+impl std::error::Error for BatchError {}
+
+// Expands a directory or a glob pattern into the input files, sorted by path.
+// Sidecar answer files are never treated as inputs.
+pub fn collect_inputs(pattern: &str) -> Result<Vec<PathBuf>, BatchError> {
+    let path = Path::new(pattern);
+    let mut files: Vec<PathBuf> = if path.is_dir() {
+        let entries = fs::read_dir(path).map_err(|err| BatchError::Io { file: path.to_path_buf(), err })?;
+        entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect()
+    } else {
+        glob::glob(pattern).map_err(BatchError::Pattern)?.filter_map(|file| file.ok()).collect()
+    };
+
+    files.retain(|file| file.is_file() && file.extension().is_none_or(|ext| ext != "answer"));
+    files.sort();
+    if files.is_empty() {
+        return Err(BatchError::NoInputs(pattern.to_string()));
+    }
+    Ok(files)
+}
+
+// The answers next to an input, e.g. big.txt -> big.answer, with the answer of
+// part N on line N. Blank lines or `-` leave a part unchecked.
+pub fn sidecar_answers(file: &Path) -> Vec<Option<String>> {
+    let Ok(content) = fs::read_to_string(file.with_extension("answer")) else {
+        return Vec::new();
+    };
+    content.lines()
+        .map(|line| line.trim())
+        .map(|line| (!line.is_empty() && line != "-").then(|| line.to_string()))
+        .collect()
+}
+
+// Runs both parts on every file in parallel, results stay in file order
+pub fn run_batch(solution: &Solution, files: &[PathBuf]) -> Vec<BatchRun> {
+    files.par_iter()
+        .map(|file| run_file(solution, file))
+        .collect::<Vec<Vec<BatchRun>>>()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+fn run_file(solution: &Solution, file: &Path) -> Vec<BatchRun> {
+    let source = file.display().to_string();
+    let input = fs::read_to_string(file).ok();
+    let expected = sidecar_answers(file);
+
+    (1..=2).map(|part| {
+        let Some(input) = input.as_ref() else {
+            let verification = Verification {
+                day: solution.day,
+                part,
+                source: source.clone(),
+                actual: None,
+                status: Status::NoInput,
+            };
+            return BatchRun { file: file.to_path_buf(), verification, parse: None, solve: None };
+        };
+
+        let mut timings: Option<(Duration, Duration)> = None;
+        let expected = expected.get(part as usize - 1).cloned().flatten();
+        let verification = verify_part(solution.day, part, source.clone(), || {
+            let run = solution.run_timed(input, part).expect("Solutions should have two parts.");
+            timings = Some((run.parse, run.solve));
+            run.answer
+        }, expected.as_deref());
+
+        BatchRun {
+            file: file.to_path_buf(),
+            verification,
+            parse: timings.map(|(parse, _)| parse),
+            solve: timings.map(|(_, solve)| solve),
+        }
+    }).collect()
+}
+
+pub fn batch_table(runs: &[BatchRun]) -> String {
+    let mut lines: Vec<String> = vec![format!(
+        "{:<30} | {:>4} | {:<8} | {:>20} | {:>12} | {:>12} | {}",
+        "File", "Part", "Status", "Answer", "Parse", "Solve", "Detail"
+    )];
+    for run in runs.iter() {
+        let verification = &run.verification;
+        let detail = verification.status.detail().unwrap_or_default();
+        let duration = |duration: Option<Duration>| duration.map(format_duration).unwrap_or_else(|| "-".to_string());
+        lines.push(format!(
+            "{:<30} | {:>4} | {:<8} | {:>20} | {:>12} | {:>12} | {}",
+            verification.source,
+            verification.part,
+            verification.status.to_string(),
+            verification.actual.as_ref().map(|actual| actual.to_string()).unwrap_or_else(|| "-".to_string()),
+            duration(run.parse),
+            duration(run.solve),
+            detail,
+        ).trim_end().to_string());
+    }
+
+    let count = |f: fn(&Status) -> bool| runs.iter().filter(|run| f(&run.verification.status)).count();
+    lines.push(format!(
+        "{} ok, {} mismatched, {} missing, {} panicked, {} failed, {} unreadable",
+        count(|s| matches!(s, Status::Correct)),
+        count(|s| matches!(s, Status::Mismatch { .. })),
+        count(|s| matches!(s, Status::Missing)),
+        count(|s| matches!(s, Status::Panicked(_))),
+        count(|s| matches!(s, Status::Failed(_))),
+        count(|s| matches!(s, Status::NoInput)),
+    ));
+    lines.join("\n")
+}
+
+pub fn batch_json(runs: &[BatchRun]) -> String {
+    serde_json::to_string_pretty(runs).expect("Batch runs should serialize.")
+}
+
+pub fn batch_csv(runs: &[BatchRun]) -> String {
+    let mut lines: Vec<String> = vec!["day,part,source,status,answer,expected,detail,parse_ns,solve_ns".to_string()];
+    for run in runs.iter() {
+        let mut fields = verification_fields(&run.verification);
+        for duration in [run.parse, run.solve] {
+            fields.push(duration.map(|duration| nanos(duration).to_string()).unwrap_or_default());
+        }
+        lines.push(fields.join(","));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::example_input;
+    use crate::registry::find_solution;
+
+    #[test]
+    fn test_run_batch() {
+        let dir = std::env::temp_dir().join(format!("aoc2023-batch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.txt"), example_input(9, "histories")).unwrap();
+        fs::write(dir.join("a.answer"), "114\n2\n").unwrap();
+        fs::write(dir.join("b.txt"), example_input(9, "histories")).unwrap();
+        fs::write(dir.join("b.answer"), "-\n3\n").unwrap();
+        fs::write(dir.join("c.txt"), "not a history").unwrap();
+
+        let files = collect_inputs(dir.to_str().unwrap()).unwrap();
+        assert_eq!(files, vec![dir.join("a.txt"), dir.join("b.txt"), dir.join("c.txt")]);
+        let pattern = dir.join("[ab].txt");
+        assert_eq!(collect_inputs(pattern.to_str().unwrap()).unwrap().len(), 2);
+
+        let runs = run_batch(&find_solution(9).unwrap(), &files);
+        let statuses: Vec<&Status> = runs.iter().map(|run| &run.verification.status).collect();
+        assert_eq!(statuses[0..4], [
+            &Status::Correct,
+            &Status::Correct,
+            &Status::Missing,
+            &Status::Mismatch { expected: "3".to_string() },
+        ]);
+        assert!(matches!(statuses[4], Status::Panicked(_)));
+        assert!(runs[0].solve.is_some() && runs[4].solve.is_none());
+        assert!(batch_table(&runs).ends_with("2 ok, 1 mismatched, 1 missing, 2 panicked, 0 failed, 0 unreadable"));
+
+        let json: serde_json::Value = serde_json::from_str(&batch_json(&runs)).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 6);
+        assert_eq!(json[0]["source"], dir.join("a.txt").display().to_string());
+        assert_eq!(json[0]["status"], "ok");
+        assert_eq!(json[0]["answer"], "114");
+        assert!(json[0]["parse_ns"].is_u64() && json[0]["solve_ns"].is_u64());
+        assert_eq!(json[3]["status"], "mismatch");
+        assert_eq!(json[3]["expected"], "3");
+        assert_eq!(json[4]["status"], "panicked");
+        assert!(json[4]["solve_ns"].is_null());
+        assert!(json[0].get("file").is_none());
+
+        let csv = batch_csv(&runs);
+        assert!(csv.starts_with("day,part,source,status,answer,expected,detail,parse_ns,solve_ns\n9,1,"));
+        assert_eq!(csv.lines().count(), 7);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
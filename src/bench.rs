@@ -0,0 +1,207 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::registry::{input_path, Solution};
+use crate::runner::time_part;
+use crate::timing::{nanos, Stats};
+
+// Timings of one phase of a day, as saved in a baseline file. The phases are
+// "parse", "part1" and "part2", where the parts leave out the parsing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub day: u32,
+    pub phase: String,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+    pub samples: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: u32,
+    pub phase: String,
+    pub baseline_ns: Option<u64>,
+    pub current_ns: u64,
+    pub change_pct: Option<f64>,
+    pub regression: bool,
+}
+
+#[derive(Debug)]
+pub enum BenchError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for BenchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BenchError::Io(err) => write!(f, "could not access baseline: {}", err),
+            BenchError::Json(err) => write!(f, "could not parse baseline: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for BenchError {}
+
+impl BenchResult {
+    fn new(day: u32, phase: &str, stats: &Stats) -> BenchResult {
+        BenchResult {
+            day,
+            phase: phase.to_string(),
+            min_ns: nanos(stats.min),
+            median_ns: nanos(stats.median),
+            p95_ns: nanos(stats.p95),
+            samples: stats.samples,
+        }
+    }
+}
+
+// Runs both parts of every solution `samples` times. Inputs are read once up
+// front so that file access never ends up in the timings, and days without an
+// input are left out, as are parts that fail. Parsing is timed along with
+// part 1, days that work straight on the input text get no parse row.
+pub fn run_benchmarks(solutions: &[Solution], samples: usize) -> Vec<BenchResult> {
+    let mut results: Vec<BenchResult> = Vec::new();
+
+    for solution in solutions.iter() {
+        let Ok(input) = fs::read_to_string(input_path(solution.day)) else {
+            continue;
+        };
+        for part in 1..=2 {
+            let Ok(report) = time_part(solution, &input, part, samples) else {
+                continue;
+            };
+            if part == 1 && solution.parses {
+                results.push(BenchResult::new(solution.day, "parse", &report.parse));
+            }
+            results.push(BenchResult::new(solution.day, format!("part{}", part).as_str(), &report.solve));
+        }
+    }
+
+    results
+}
+
+pub fn save_baseline(path: &Path, results: &[BenchResult]) -> Result<(), BenchError> {
+    let json = serde_json::to_string_pretty(results).map_err(BenchError::Json)?;
+    fs::write(path, json + "\n").map_err(BenchError::Io)
+}
+
+pub fn load_baseline(path: &Path) -> Result<Vec<BenchResult>, BenchError> {
+    let content = fs::read_to_string(path).map_err(BenchError::Io)?;
+    serde_json::from_str(&content).map_err(BenchError::Json)
+}
+
+// Compares medians, a phase regressed when it got slower by more than
+// `threshold_pct` percent
+pub fn compare(baseline: &[BenchResult], current: &[BenchResult], threshold_pct: f64) -> Vec<Comparison> {
+    current.iter().map(|result| {
+        let previous = baseline.iter()
+            .find(|b| b.day == result.day && b.phase == result.phase)
+            .map(|b| b.median_ns);
+        let change_pct = previous
+            .filter(|&ns| ns > 0)
+            .map(|ns| (result.median_ns as f64 - ns as f64) / ns as f64 * 100.0);
+
+        Comparison {
+            day: result.day,
+            phase: result.phase.clone(),
+            baseline_ns: previous,
+            current_ns: result.median_ns,
+            change_pct,
+            regression: change_pct.is_some_and(|change| change > threshold_pct),
+        }
+    }).collect()
+}
+
+pub fn results_table(results: &[BenchResult]) -> String {
+    let mut lines: Vec<String> = vec![format!(
+        "{:>3} | {:<5} | {:>14} | {:>14} | {:>14} | {:>7}",
+        "day", "phase", "min (ns)", "median (ns)", "p95 (ns)", "samples"
+    )];
+    for r in results.iter() {
+        lines.push(format!(
+            "{:>3} | {:<5} | {:>14} | {:>14} | {:>14} | {:>7}",
+            r.day, r.phase, r.min_ns, r.median_ns, r.p95_ns, r.samples
+        ));
+    }
+    lines.join("\n")
+}
+
+pub fn comparison_table(comparisons: &[Comparison]) -> String {
+    let mut lines: Vec<String> = vec![format!(
+        "{:>3} | {:<5} | {:>14} | {:>14} | {:>8} | {}",
+        "day", "phase", "baseline (ns)", "median (ns)", "change", "status"
+    )];
+    for c in comparisons.iter() {
+        let baseline = c.baseline_ns.map(|ns| ns.to_string()).unwrap_or_else(|| "-".to_string());
+        let change = c.change_pct.map(|pct| format!("{:+.1}%", pct)).unwrap_or_else(|| "-".to_string());
+        let status = if c.regression {
+            "REGRESSION"
+        } else if c.baseline_ns.is_none() {
+            "new"
+        } else {
+            "ok"
+        };
+        lines.push(format!(
+            "{:>3} | {:<5} | {:>14} | {:>14} | {:>8} | {}",
+            c.day, c.phase, baseline, c.current_ns, change, status
+        ));
+    }
+
+    let regressions = comparisons.iter().filter(|c| c.regression).count();
+    lines.push(format!("{} compared, {} regressed", comparisons.len(), regressions));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::find_solution;
+
+    fn result(day: u32, phase: &str, median_ns: u64) -> BenchResult {
+        BenchResult { day, phase: phase.to_string(), min_ns: median_ns, median_ns, p95_ns: median_ns, samples: 1 }
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = vec![result(1, "part1", 1000), result(1, "part2", 1000)];
+        let current = vec![result(1, "part1", 1050), result(1, "part2", 1200), result(2, "part1", 10)];
+        let comparisons = compare(&baseline, &current, 10.0);
+
+        assert!(!comparisons[0].regression);
+        assert_eq!(comparisons[0].change_pct, Some(5.0));
+        assert!(comparisons[1].regression);
+        assert_eq!(comparisons[2].baseline_ns, None);
+        assert!(!comparisons[2].regression);
+        assert!(comparison_table(&comparisons).ends_with("3 compared, 1 regressed"));
+    }
+
+    #[test]
+    fn test_run_benchmarks() {
+        let solutions: Vec<Solution> = [1, 9].iter().map(|day| find_solution(*day).unwrap()).collect();
+        let phases: Vec<(u32, String)> = run_benchmarks(&solutions, 1).into_iter()
+            .map(|result| (result.day, result.phase))
+            .collect();
+        // Day 1 works straight on the input, so it has nothing to parse
+        assert_eq!(phases, vec![
+            (1, "part1".to_string()),
+            (1, "part2".to_string()),
+            (9, "parse".to_string()),
+            (9, "part1".to_string()),
+            (9, "part2".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let path = std::env::temp_dir().join(format!("aoc2023-baseline-{}.json", std::process::id()));
+        let results = vec![result(3, "parse", 42), result(3, "part1", 7)];
+        save_baseline(&path, &results).unwrap();
+        assert_eq!(load_baseline(&path).unwrap(), results);
+        fs::remove_file(&path).unwrap();
+    }
+}
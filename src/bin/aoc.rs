@@ -0,0 +1,254 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+use aoc2023::answers::{summary_table, verifications_csv, verifications_json, verify, Answers, Verification};
+use aoc2023::batch::{batch_csv, batch_json, batch_table, collect_inputs, run_batch};
+use aoc2023::bench::{compare, comparison_table, load_baseline, results_table, run_benchmarks, save_baseline};
+use aoc2023::examples::run_examples;
+use aoc2023::registry::{find_solution, solutions, Solution};
+use aoc2023::runner::{reports_csv, reports_json, run_solution, RunReport};
+use aoc2023::scaffold::scaffold_day;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2023 runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(about = "Run solutions on their inputs with the load, parse and solve phases timed")]
+    Run {
+        #[arg(long)]
+        day: Option<u32>,
+        #[arg(long)]
+        part: Option<u32>,
+        #[arg(long, default_value_t = 1, help = "Run each part this many times and report min, median and p95")]
+        repeat: usize,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    #[command(about = "Run both parts of a day on every input in a directory or glob, in parallel")]
+    Batch {
+        #[arg(long)]
+        day: u32,
+        #[arg(help = "Directory or glob pattern, answers are checked against sidecar .answer files")]
+        inputs: String,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    #[command(about = "Check every registered solution against the recorded answers")]
+    Verify {
+        #[arg(long)]
+        day: Option<u32>,
+        #[arg(long, default_value = "data/answers.toml")]
+        answers: PathBuf,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    #[command(about = "Run the puzzle examples against the answers in data/examples")]
+    Examples {
+        #[arg(long)]
+        day: Option<u32>,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    #[command(about = "Time the parse and solve phases of each day, optionally against a saved baseline")]
+    Bench {
+        #[arg(long)]
+        day: Option<u32>,
+        #[arg(long, default_value_t = 10)]
+        samples: usize,
+        #[arg(long, help = "Write the timings to this JSON file")]
+        save: Option<PathBuf>,
+        #[arg(long, help = "Compare against timings saved earlier with --save")]
+        baseline: Option<PathBuf>,
+        #[arg(long, default_value_t = 10.0, help = "Slowdown in percent that counts as a regression")]
+        threshold: f64,
+    },
+    #[command(about = "Generate the module, binary, bench and examples for a new day")]
+    New {
+        #[arg(long)]
+        day: u32,
+    },
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Run { day, part, repeat, format } => run_parts(day, part, repeat, format),
+        Command::Batch { day, inputs, format } => run_batch_command(day, inputs, format),
+        Command::Verify { day, answers, format } => run_verify(day, answers, format),
+        Command::Examples { day, format } => run_examples_command(day, format),
+        Command::Bench { day, samples, save, baseline, threshold } => {
+            run_bench(day, samples, save, baseline, threshold)
+        },
+        Command::New { day } => run_new(day),
+    }
+}
+
+fn run_parts(day: Option<u32>, part: Option<u32>, repeat: usize, format: Format) -> ExitCode {
+    let Some(solutions) = select_solutions(day) else {
+        return ExitCode::FAILURE;
+    };
+    let parts: Vec<u32> = part.map(|part| vec![part]).unwrap_or_else(|| vec![1, 2]);
+
+    let mut reports: Vec<RunReport> = Vec::new();
+    for solution in solutions.iter() {
+        for part in parts.iter() {
+            match run_solution(solution, *part, repeat) {
+                Ok(report) if format == Format::Text => println!("{}", report.text()),
+                Ok(report) => reports.push(report),
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return ExitCode::FAILURE;
+                },
+            }
+        }
+    }
+
+    match format {
+        Format::Text => {},
+        Format::Json => println!("{}", reports_json(&reports)),
+        Format::Csv => println!("{}", reports_csv(&reports)),
+    }
+    ExitCode::SUCCESS
+}
+
+fn run_batch_command(day: u32, inputs: String, format: Format) -> ExitCode {
+    let Some(solutions) = select_solutions(Some(day)) else {
+        return ExitCode::FAILURE;
+    };
+    let files = match collect_inputs(&inputs) {
+        Ok(files) => files,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        },
+    };
+
+    let runs = run_batch(&solutions[0], &files);
+    match format {
+        Format::Text => println!("{}", batch_table(&runs)),
+        Format::Json => println!("{}", batch_json(&runs)),
+        Format::Csv => println!("{}", batch_csv(&runs)),
+    }
+    if runs.iter().any(|run| run.verification.is_failure()) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn run_verify(day: Option<u32>, answers: PathBuf, format: Format) -> ExitCode {
+    let answers = match Answers::load(&answers) {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        },
+    };
+    let Some(solutions) = select_solutions(day) else {
+        return ExitCode::FAILURE;
+    };
+    report(&verify(&solutions, &answers), format)
+}
+
+fn run_examples_command(day: Option<u32>, format: Format) -> ExitCode {
+    let Some(solutions) = select_solutions(day) else {
+        return ExitCode::FAILURE;
+    };
+    let verifications: Vec<Verification> = solutions.iter().flat_map(run_examples).collect();
+    report(&verifications, format)
+}
+
+fn run_bench(
+    day: Option<u32>,
+    samples: usize,
+    save: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+) -> ExitCode {
+    let Some(solutions) = select_solutions(day) else {
+        return ExitCode::FAILURE;
+    };
+    let results = run_benchmarks(&solutions, samples.max(1));
+
+    if let Some(path) = save {
+        if let Err(err) = save_baseline(&path, &results) {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let Some(path) = baseline else {
+        println!("{}", results_table(&results));
+        return ExitCode::SUCCESS;
+    };
+    let baseline = match load_baseline(&path) {
+        Ok(baseline) => baseline,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        },
+    };
+    let comparisons = compare(&baseline, &results, threshold);
+    println!("{}", comparison_table(&comparisons));
+
+    if comparisons.iter().any(|c| c.regression) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn run_new(day: u32) -> ExitCode {
+    match scaffold_day(Path::new("."), day) {
+        Ok(files) => {
+            for file in files.iter() {
+                println!("{}", file.display());
+            }
+            ExitCode::SUCCESS
+        },
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        },
+    }
+}
+
+fn select_solutions(day: Option<u32>) -> Option<Vec<Solution>> {
+    match day {
+        None => Some(solutions()),
+        Some(day) => match find_solution(day) {
+            Some(solution) => Some(vec![solution]),
+            None => {
+                eprintln!("No solution registered for day {}", day);
+                None
+            },
+        },
+    }
+}
+
+fn report(verifications: &[Verification], format: Format) -> ExitCode {
+    match format {
+        Format::Text => println!("{}", summary_table(verifications)),
+        Format::Json => println!("{}", verifications_json(verifications)),
+        Format::Csv => println!("{}", verifications_csv(verifications)),
+    }
+
+    if verifications.iter().any(|v| v.is_failure()) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -1,7 +1,4 @@
-use std::{fs, path::PathBuf};
-use std::path::Path;
-
-use aoc2023::day01::{part1, part2};
+use aoc2023::runner::run_day;
 
 fn main() {
     run_part1();
@@ -9,17 +6,13 @@ fn main() {
 }
 
 pub fn run_part1() {
-    let filename: PathBuf = Path::new("data").join("day01-input.txt");
-    let input_string = fs::read_to_string(filename).unwrap();
-    let value = part1(input_string.as_str());
-    println!("Calibration value total: v1: {}", value);
+    let report = run_day(1, 1, 1).expect("Day 1 should run.");
+    println!("Calibration value total: v1: {}", report.answer);
+    println!("Duration: {}", report.duration_summary());
 }
 
 pub fn run_part2() {
-    let filename: PathBuf = Path::new("data").join("day01-input.txt");
-    let input_string = fs::read_to_string(filename).unwrap();
-    let value = part2(input_string.as_str());
-
-    println!("Calibration value total: v2: {}", value);
+    let report = run_day(1, 2, 1).expect("Day 1 should run.");
+    println!("Calibration value total: v2: {}", report.answer);
+    println!("Duration: {}", report.duration_summary());
 }
-
@@ -1,7 +1,4 @@
-use std::{fs, path::PathBuf};
-use std::path::Path;
-
-use aoc2023::day02::{part1, part2, CubeSet};
+use aoc2023::runner::run_day;
 
 fn main() {
     run_part1();
@@ -9,20 +6,13 @@ fn main() {
 }
 
 pub fn run_part1() {
-    let filename: PathBuf = Path::new("data").join("day02-input.txt");
-    let input_string = fs::read_to_string(filename).unwrap();
-    let cube_set = CubeSet {
-        red: 12,
-        green: 13,
-        blue: 14,
-    };
-    let value = part1(input_string.as_str(), cube_set);
-    println!("Total games: {}", value);
+    let report = run_day(2, 1, 1).expect("Day 2 should run.");
+    println!("Total games: {}", report.answer);
+    println!("Duration: {}", report.duration_summary());
 }
 
 pub fn run_part2() {
-    let filename: PathBuf = Path::new("data").join("day02-input.txt");
-    let input_string = fs::read_to_string(filename).unwrap();
-    let value = part2(input_string.as_str());
-    println!("Sum of min power: {}", value);
+    let report = run_day(2, 2, 1).expect("Day 2 should run.");
+    println!("Sum of min power: {}", report.answer);
+    println!("Duration: {}", report.duration_summary());
 }
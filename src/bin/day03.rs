@@ -1,30 +1,40 @@
-use std::time::Instant;
 use std::{fs, path::PathBuf};
 use std::path::Path;
 
-use aoc2023::day03::{part1, part2};
+use aoc2023::day03::{GearRule, RenderStyle, Schematic};
+use aoc2023::runner::run_day;
 
 fn main() {
+    // Pass ansi, html or json to see which numbers were counted
+    if let Some(format) = std::env::args().nth(1) {
+        render(format.as_str());
+        return;
+    }
+
     run_part1();
     run_part2();
 }
 
-pub fn run_part1() {
+pub fn render(format: &str) {
     let filename: PathBuf = Path::new("data").join("day03-input.txt");
     let input_string = fs::read_to_string(filename).unwrap();
-    let ts = Instant::now();
-    let value = part1(input_string.as_str());
-    let duration = ts.elapsed().as_millis();
-    println!("Total of part numbers: {}", value);
-    println!("Duration: {} ms", duration);
+    let schematic = Schematic::parse(input_string.as_str());
+    match format {
+        "ansi" => println!("{}", schematic.render(RenderStyle::Ansi, &GearRule::standard())),
+        "html" => println!("{}", schematic.render(RenderStyle::Html, &GearRule::standard())),
+        "json" => println!("{}", schematic.number_reports_json()),
+        _ => eprintln!("Unknown format: {}, expected ansi, html or json", format),
+    }
+}
+
+pub fn run_part1() {
+    let report = run_day(3, 1, 1).expect("Day 3 should run.");
+    println!("Total of part numbers: {}", report.answer);
+    println!("Duration: {}", report.duration_summary());
 }
 
 pub fn run_part2() {
-    let filename: PathBuf = Path::new("data").join("day03-input.txt");
-    let input_string = fs::read_to_string(filename).unwrap();
-    let ts = Instant::now();
-    let value = part2(input_string.as_str());
-    let duration = ts.elapsed().as_millis();
-    println!("Total gears: {}", value);
-    println!("Duration: {} ms", duration);
+    let report = run_day(3, 2, 1).expect("Day 3 should run.");
+    println!("Total gears: {}", report.answer);
+    println!("Duration: {}", report.duration_summary());
 }
@@ -1,23 +1,48 @@
 use std::{fs, path::PathBuf};
 use std::path::Path;
 
-use aoc2023::day04::{part1, part2};
+use num::BigUint;
+
+use aoc2023::day04::{audit_cards, audit_json, audit_table, parse_cards};
+use aoc2023::runner::run_day;
 
 fn main() {
+    // Pass table or json to see how each card got its copies
+    if let Some(format) = std::env::args().nth(1) {
+        audit(format.as_str());
+        return;
+    }
+
     run_part1();
     run_part2();
 }
 
-pub fn run_part1() {
+pub fn audit(format: &str) {
     let filename: PathBuf = Path::new("data").join("day04-input.txt");
     let input_string = fs::read_to_string(filename).unwrap();
-    let value = part1(input_string.as_str());
-    println!("Winning points: {}", value);
+    let cards = parse_cards(input_string.as_str()).expect("Cards should be valid.");
+    let audits = match audit_cards::<BigUint>(&cards) {
+        Ok(audits) => audits,
+        Err(err) => {
+            eprintln!("{}", err);
+            return;
+        },
+    };
+    match format {
+        "table" => println!("{}", audit_table(&audits)),
+        "json" => println!("{}", audit_json(&audits)),
+        _ => eprintln!("Unknown format: {}, expected table or json", format),
+    }
+}
+
+pub fn run_part1() {
+    let report = run_day(4, 1, 1).expect("Day 4 should run.");
+    println!("Winning points: {}", report.answer);
+    println!("Duration: {}", report.duration_summary());
 }
 
 pub fn run_part2() {
-    let filename: PathBuf = Path::new("data").join("day04-input.txt");
-    let input_string = fs::read_to_string(filename).unwrap();
-    let value = part2(input_string.as_str());
-    println!("Total cards: {}", value);
+    let report = run_day(4, 2, 1).expect("Day 4 should run.");
+    println!("Total cards: {}", report.answer);
+    println!("Duration: {}", report.duration_summary());
 }
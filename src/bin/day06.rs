@@ -1,8 +1,4 @@
-use std::time::Instant;
-use std::{fs, path::PathBuf};
-use std::path::Path;
-
-use aoc2023::day06::{part1, part2};
+use aoc2023::runner::run_day;
 
 fn main() {
     run_part1();
@@ -10,21 +6,13 @@ fn main() {
 }
 
 pub fn run_part1() {
-    let filename: PathBuf = Path::new("data").join("day06-input.txt");
-    let input_string = fs::read_to_string(filename).unwrap();
-    let ts = Instant::now();
-    let value = part1(input_string.as_str());
-    let duration = ts.elapsed().as_millis();
-    println!("Product of total ways: {}", value);
-    println!("Duration: {} ms", duration);
+    let report = run_day(6, 1, 1).expect("Day 6 should run.");
+    println!("Product of total ways: {}", report.answer);
+    println!("Duration: {}", report.duration_summary());
 }
 
 pub fn run_part2() {
-    let filename: PathBuf = Path::new("data").join("day06-input.txt");
-    let input_string = fs::read_to_string(filename).unwrap();
-    let ts = Instant::now();
-    let value = part2(input_string.as_str());
-    let duration = ts.elapsed().as_millis();
-    println!("Product of total ways: {}", value);
-    println!("Duration: {} ms", duration);
+    let report = run_day(6, 2, 1).expect("Day 6 should run.");
+    println!("Product of total ways: {}", report.answer);
+    println!("Duration: {}", report.duration_summary());
 }
@@ -0,0 +1,80 @@
+use std::fmt::Display;
+
+use num::{CheckedAdd, CheckedMul, CheckedSub, One, Zero};
+
+use crate::error::AocError;
+
+// Arithmetic for the sums and products that can outgrow their type. Built with
+// the `checked` feature, an overflow comes back as AocError::Overflow naming
+// what was being computed. Without it these are the plain operators, which
+// wrap in release builds.
+pub const ENABLED: bool = cfg!(feature = "checked");
+
+pub fn add<T: CheckedAdd + Display>(a: T, b: T, context: &str) -> Result<T, AocError> {
+    if ENABLED {
+        a.checked_add(&b).ok_or_else(|| overflow(context, &a, "+", &b))
+    } else {
+        Ok(a + b)
+    }
+}
+
+pub fn sub<T: CheckedSub + Display>(a: T, b: T, context: &str) -> Result<T, AocError> {
+    if ENABLED {
+        a.checked_sub(&b).ok_or_else(|| overflow(context, &a, "-", &b))
+    } else {
+        Ok(a - b)
+    }
+}
+
+pub fn mul<T: CheckedMul + Display>(a: T, b: T, context: &str) -> Result<T, AocError> {
+    if ENABLED {
+        a.checked_mul(&b).ok_or_else(|| overflow(context, &a, "*", &b))
+    } else {
+        Ok(a * b)
+    }
+}
+
+pub fn sum<T, I>(values: I, context: &str) -> Result<T, AocError>
+where
+    T: CheckedAdd + Zero + Display,
+    I: IntoIterator<Item = T>,
+{
+    values.into_iter().try_fold(T::zero(), |total, value| add(total, value, context))
+}
+
+pub fn product<T, I>(values: I, context: &str) -> Result<T, AocError>
+where
+    T: CheckedMul + One + Display,
+    I: IntoIterator<Item = T>,
+{
+    values.into_iter().try_fold(T::one(), |total, value| mul(total, value, context))
+}
+
+fn overflow<T: Display>(context: &str, a: &T, op: &str, b: &T) -> AocError {
+    AocError::Overflow { context: format!("{} ({} {} {})", context, a, op, b) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_arithmetic() {
+        assert_eq!(add(2u32, 3, "test"), Ok(5));
+        assert_eq!(product([2u64, 3, 7], "test"), Ok(42));
+        assert_eq!(sum(Vec::<u8>::new(), "test"), Ok(0));
+
+        if ENABLED {
+            assert_eq!(
+                mul(u32::MAX, 2, "gear ratio"),
+                Err(AocError::Overflow { context: format!("gear ratio ({} * 2)", u32::MAX) }),
+            );
+            assert!(sum([u8::MAX, 1], "total").is_err());
+            assert!(sub(0u64, 1, "running copies").is_err());
+            assert_eq!(
+                AocError::Overflow { context: "total (255 + 1)".to_string() }.to_string(),
+                "arithmetic overflow in total (255 + 1)",
+            );
+        }
+    }
+}
@@ -1,4 +1,8 @@
-const SPELLED_DIGITS: [&'static str; 9] = [
+use std::collections::VecDeque;
+use rayon::prelude::*;
+use serde::Serialize;
+
+const SPELLED_DIGITS: [&str; 9] = [
     "one",
     "two",
     "three",
@@ -10,155 +14,310 @@ const SPELLED_DIGITS: [&'static str; 9] = [
     "nine",
 ];
 
-pub fn part1(input: &str) -> u32 {
-    // Input as string with multiple lines
-    // Each line may contain digits, combine the first and last digit to form a single
-    // two-digit number
-    // There may be more than 2 digits, only pick the first and last
-    // Ignore other characters
-    // If there is only 1 digit found, repeat it
-    extract_total_calibration_values(input)
-}
+// Zero of each run of Unicode decimal digits (general category Nd) in the
+// Basic Multilingual Plane, each followed by the digits one to nine
+const UNICODE_DIGIT_ZEROS: [u32; 36] = [
+    0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66,
+    0x0CE6, 0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810,
+    0x1946, 0x19D0, 0x1A80, 0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0,
+    0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0, 0xFF10,
+];
 
-pub fn part2(input: &str) -> u32 {
-    // Input as string with multiple lines
-    // Each line may contain digits, combine the first and last digit to form a single
-    // two-digit number
-    // There may be more than 2 digits, only pick the first and last
-    // Digits can be spelled out too, that's crazy!
-    // Reminds me of my old certification exam where I failed converting spelled out numbers into
-    // digits
-    // Ignore other characters
-    // If there is only 1 digit found, repeat it
-    extract_total_calibration_values_v2(input)
+// Tokens to look for in a calibration line and the digit each one stands for
+#[derive(Debug, Clone, Default)]
+pub struct Vocabulary {
+    pub tokens: Vec<(String, u32)>,
 }
 
-fn extract_total_calibration_values(input: &str) -> u32 {
-    let mut total: u32 = 0;
-    for line in input.lines() {
-        // Collect digits for each line
-        let digit = extract_line_value(line);
-        total += digit;
+impl Vocabulary {
+    pub fn new() -> Self {
+        Vocabulary::default()
     }
-    return total;
-}
 
-fn extract_line_value(line: &str) -> u32 {
-    let mut digits: Vec<u32> = Vec::new();
-    for char in line.chars() {
-        if let Some(digit) = char.to_digit(10) {
-            digits.push(digit);
-        }
+    // Plain digits 0-9
+    pub fn digits() -> Self {
+        Vocabulary::new().with_words(&["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"], 0)
+    }
+
+    // Plain digits and the spelled out digits one to nine
+    pub fn english() -> Self {
+        Vocabulary::digits().with_words(&SPELLED_DIGITS, 1)
     }
 
-    if digits.len() >= 2 {
-        let tens = digits[0] * 10;
-        let ones = digits[digits.len() - 1];
-        return tens + ones;
-    } else if digits.len() == 1 {
-        // Simply repeat the digit
-        return (digits[0] * 10) + digits[0];
-    } else {
-        return 0;
+    // Decimal digits from other scripts, ie: Arabic-Indic "٣" or fullwidth "３"
+    pub fn with_unicode_digits(self) -> Self {
+        UNICODE_DIGIT_ZEROS.iter().fold(self, |vocabulary, zero| {
+            (0..10).fold(vocabulary, |vocabulary, value| {
+                let digit = char::from_u32(zero + value).expect("Digit should be a valid char.");
+                vocabulary.with_token(digit.to_string().as_str(), value)
+            })
+        })
     }
+
+    pub fn with_token(mut self, token: &str, value: u32) -> Self {
+        self.tokens.push((token.to_string(), value));
+        self
+    }
+
+    // Adds consecutive words counting up from the first value,
+    // ie: ["uno", "dos", "tres"] starting at 1
+    pub fn with_words(self, words: &[&str], first_value: u32) -> Self {
+        words.iter().enumerate().fold(self, |vocabulary, (i, word)| {
+            vocabulary.with_token(word, first_value + i as u32)
+        })
+    }
+}
+
+// A token found in a line with its byte span. Tokens are matched as whole
+// UTF-8 sequences so the span always falls on char boundaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Token {
+    pub value: u32,
+    pub start: usize,
+    pub end: usize,
+}
+
+// What the scanner found on one line. Offset is where the line starts in the
+// input, token spans are relative to the line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LineReport {
+    pub line: usize,
+    pub offset: usize,
+    pub first: Option<Token>,
+    pub last: Option<Token>,
+    pub value: u32,
 }
 
-fn extract_total_calibration_values_v2(input: &str) -> u32 {
-    let mut total: u32 = 0;
-    for line in input.lines() {
-        // Collect digits for each line
-        let digit = extract_line_value_v2(line);
-        total += digit;
+// Aho-Corasick automaton over bytes with every transition precomputed, so
+// scanning is a single table lookup per byte. Each state remembers the
+// longest token that ends there, following the failure links.
+#[derive(Debug, Clone)]
+struct Automaton {
+    transitions: Vec<[usize; 256]>,
+    outputs: Vec<Option<(usize, u32)>>,
+}
+
+impl Automaton {
+    fn new(tokens: impl Iterator<Item = (Vec<u8>, u32)>) -> Self {
+        let mut transitions: Vec<[usize; 256]> = vec![[0; 256]];
+        let mut has_edge: Vec<[bool; 256]> = vec![[false; 256]];
+        let mut outputs: Vec<Option<(usize, u32)>> = vec![None];
+
+        // Build the trie first
+        for (token, value) in tokens {
+            if token.is_empty() {
+                continue;
+            }
+            let mut state: usize = 0;
+            for byte in token.iter() {
+                let byte = *byte as usize;
+                if !has_edge[state][byte] {
+                    transitions.push([0; 256]);
+                    has_edge.push([false; 256]);
+                    outputs.push(None);
+                    transitions[state][byte] = transitions.len() - 1;
+                    has_edge[state][byte] = true;
+                }
+                state = transitions[state][byte];
+            }
+            // First token wins when the same text is added twice
+            if outputs[state].is_none() {
+                outputs[state] = Some((token.len(), value));
+            }
+        }
+
+        // Breadth first, fill the missing transitions from the failure state
+        let mut fail: Vec<usize> = vec![0; transitions.len()];
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        for byte in 0..256 {
+            if has_edge[0][byte] {
+                queue.push_back(transitions[0][byte]);
+            }
+        }
+
+        while let Some(state) = queue.pop_front() {
+            if outputs[state].is_none() {
+                outputs[state] = outputs[fail[state]];
+            }
+            for byte in 0..256 {
+                let fallback = transitions[fail[state]][byte];
+                if has_edge[state][byte] {
+                    let next = transitions[state][byte];
+                    fail[next] = fallback;
+                    queue.push_back(next);
+                } else {
+                    transitions[state][byte] = fallback;
+                }
+            }
+        }
+
+        Automaton {
+            transitions,
+            outputs,
+        }
     }
-    return total;
 }
 
-fn extract_line_value_v2(line: &str) -> u32 {
-    // Extract digits, but digits can be spelled out 0-9 digits
-    // To to this, we need to walk from start to end
-    // We will use a start and end pointer
-    // The end pointer moves one character at a time
-    // Detect a digit or a spelled digit
-    // If a digit is detected, collect the digit and move the start pointer in front of the digit
-    // If a spelled digit is detected, collect the digit and move the start pointer
-    // in front of first char, excluding it, in case there are overlapping words
-    let mut digits: Vec<u32> = Vec::new();
-    let mut start: usize = 0;
-    let mut end: usize;
-
-    for (i, char) in line.chars().enumerate() {
-        end = i;
-        if let Some(digit) = char.to_digit(10) {
-            digits.push(digit);
-            // Found a digit, move start to current index
-            start = i + 1;
-        } else {
-            // Try if start to end contains any of the spelled digits
-            if end > start && end < line.len() {
-                let word = &line[start..=end];
-                if let Some(digit_info) = find_spelled_digit(word) {
-                    digits.push(digit_info.0);
-                    // Find a spelled digit, move start to the last index of that word
-                    start = start + digit_info.1 + 1;
+// Finds the first and last token of a line. The first token is found scanning
+// forward and the last one scanning backward with the tokens reversed, so a
+// line is read at most once from each end. Overlapping tokens like "eighthree"
+// need no special handling.
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    forward: Automaton,
+    reverse: Automaton,
+    max_len: usize,
+}
+
+impl Scanner {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        let tokens = || vocabulary.tokens.iter().map(|(token, value)| (token.as_bytes().to_vec(), *value));
+        Scanner {
+            forward: Automaton::new(tokens()),
+            reverse: Automaton::new(tokens().map(|(mut token, value)| {
+                token.reverse();
+                (token, value)
+            })),
+            max_len: vocabulary.tokens.iter().map(|(token, _)| token.len()).max().unwrap_or(0),
+        }
+    }
+
+    pub fn first(&self, line: &str) -> Option<Token> {
+        let mut state: usize = 0;
+        let mut found: Option<Token> = None;
+
+        for (i, byte) in line.bytes().enumerate() {
+            // Nothing that ends from here on can start before what we have
+            if let Some(token) = found {
+                if i >= token.start + self.max_len {
+                    break;
+                }
+            }
+
+            state = self.forward.transitions[state][byte as usize];
+            if let Some((len, value)) = self.forward.outputs[state] {
+                let start = i + 1 - len;
+                if found.is_none_or(|token| start < token.start) {
+                    found = Some(Token { value, start, end: i + 1 });
                 }
             }
         }
+
+        found
     }
 
-    if digits.len() >= 2 {
-        let tens = digits[0] * 10;
-        let ones = digits[digits.len() - 1];
-        return tens + ones;
-    } else if digits.len() == 1 {
-        // Simply repeat the digit
-        return (digits[0] * 10) + digits[0];
-    } else {
-        return 0;
+    pub fn last(&self, line: &str) -> Option<Token> {
+        // The first match from the back is the one that starts the latest
+        let mut state: usize = 0;
+        for (i, byte) in line.bytes().enumerate().rev() {
+            state = self.reverse.transitions[state][byte as usize];
+            if let Some((len, value)) = self.reverse.outputs[state] {
+                return Some(Token { value, start: i, end: i + len });
+            }
+        }
+        None
     }
-}
 
-// Return the digit value and the index of the first char of that spelled digit
-fn find_spelled_digit(line: &str) -> Option<(u32, usize)> {
-    let mut result: Option<(u32, usize)> = None;
-    for (i, word) in SPELLED_DIGITS.iter().enumerate() {
-        if let Some(index) = line.find(*word) {
-            let value: u32 = i as u32 + 1;
-            result = Some((value, index));
-            break;
+    // Combine the first and last token into a two-digit number
+    pub fn line_value(&self, line: &str) -> u32 {
+        match (self.first(line), self.last(line)) {
+            (Some(first), Some(last)) => first.value * 10 + last.value,
+            _ => 0,
         }
+    }
 
+    pub fn total(&self, input: &str) -> u64 {
+        input.lines().map(|line| self.line_value(line) as u64).sum()
     }
-    result
+
+    // Same as total, but the input is cut into chunks of about chunk_size bytes
+    // that end on a line break, and the chunks are scanned in parallel
+    pub fn total_parallel(&self, input: &str, chunk_size: usize) -> u64 {
+        split_line_chunks(input, chunk_size).par_iter().map(|chunk| self.total(chunk)).sum()
+    }
+
+    pub fn report(&self, input: &str) -> Vec<LineReport> {
+        let mut offset: usize = 0;
+        input.split_inclusive('\n').enumerate().map(|(i, raw)| {
+            let line = raw.strip_suffix('\n').unwrap_or(raw);
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            let first = self.first(line);
+            let last = self.last(line);
+            let report = LineReport {
+                line: i + 1,
+                offset,
+                first,
+                last,
+                value: self.line_value(line),
+            };
+            offset += raw.len();
+            report
+        }).collect()
+    }
+}
+
+// Cuts the input into pieces of at least chunk_size bytes, each extended up to
+// and including the next line break so no line is split
+pub fn split_line_chunks(input: &str, chunk_size: usize) -> Vec<&str> {
+    let mut chunks: Vec<&str> = Vec::new();
+    let mut rest = input;
+
+    while !rest.is_empty() {
+        let cut = match rest.len() > chunk_size {
+            true => rest.as_bytes()[chunk_size..].iter()
+                .position(|byte| *byte == b'\n')
+                .map(|pos| chunk_size + pos + 1)
+                .unwrap_or(rest.len()),
+            false => rest.len(),
+        };
+        let (chunk, tail) = rest.split_at(cut);
+        chunks.push(chunk);
+        rest = tail;
+    }
+
+    chunks
+}
+
+pub fn part1(input: &str) -> u64 {
+    // Input as string with multiple lines
+    // Each line may contain digits, combine the first and last digit to form a single
+    // two-digit number
+    // There may be more than 2 digits, only pick the first and last
+    // Ignore other characters
+    // If there is only 1 digit found, repeat it
+    extract_total_calibration_values(input)
+}
+
+pub fn part2(input: &str) -> u64 {
+    // Input as string with multiple lines
+    // Each line may contain digits, combine the first and last digit to form a single
+    // two-digit number
+    // There may be more than 2 digits, only pick the first and last
+    // Digits can be spelled out too, that's crazy!
+    // Reminds me of my old certification exam where I failed converting spelled out numbers into
+    // digits
+    // Ignore other characters
+    // If there is only 1 digit found, repeat it
+    extract_total_calibration_values_v2(input)
+}
+
+fn extract_total_calibration_values(input: &str) -> u64 {
+    Scanner::new(&Vocabulary::digits()).total(input)
+}
+
+fn extract_total_calibration_values_v2(input: &str) -> u64 {
+    Scanner::new(&Vocabulary::english()).total(input)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::example_input;
 
-    #[test]
-    fn test_day1_puzzle01() {
-        let lines = "1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
-treb7uchet";
-
-        let total = extract_total_calibration_values(lines);
-        assert_eq!(total, 142);
-    }
+    crate::example_tests!(1);
 
     #[test]
     fn test_day1_puzzle02() {
-        let lines = "
-two1nine
-eightwothree
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen";
-        let total = extract_total_calibration_values_v2(lines);
-        assert_eq!(total, 281);
-
         // Test another
         let lines2 = "eightfivesssxxmgthreethreeone1sevenhnz";
         let total2 = extract_total_calibration_values_v2(lines2);
@@ -173,4 +332,108 @@ zoneight234
         let total4 = extract_total_calibration_values_v2(lines4);
         assert_eq!(total4, 79);
     }
+
+    #[test]
+    fn test_scanner_tokens() {
+        let scanner = Scanner::new(&Vocabulary::english());
+        assert_eq!(scanner.first("xtwone3four"), Some(Token { value: 2, start: 1, end: 4 }));
+        assert_eq!(scanner.last("xtwone3four"), Some(Token { value: 4, start: 7, end: 11 }));
+        assert_eq!(scanner.last("eighthree"), Some(Token { value: 3, start: 4, end: 9 }));
+        assert_eq!(scanner.first("nothing here"), None);
+        assert_eq!(scanner.line_value("nothing here"), 0);
+
+        // A short token inside a longer one that starts earlier
+        let scanner = Scanner::new(&Vocabulary::new().with_token("abcd", 1).with_token("bc", 2));
+        assert_eq!(scanner.first("xabcd").map(|token| token.value), Some(1));
+        assert_eq!(scanner.last("xabcd").map(|token| token.value), Some(2));
+    }
+
+    #[test]
+    fn test_scanner_utf8() {
+        // Multi-byte chars before, between and after the digits
+        let scanner = Scanner::new(&Vocabulary::english());
+        let line = "café7naïveéight€";
+        let first = scanner.first(line).unwrap();
+        let last = scanner.last(line).unwrap();
+        assert_eq!(&line[first.start..first.end], "7");
+        assert_eq!(&line[last.start..last.end], "7");
+        assert_eq!(scanner.line_value(line), 77);
+        assert_eq!(scanner.total("né1e\n😀two😀\nçà"), 11 + 22);
+
+        let portuguese = Vocabulary::digits()
+            .with_words(&["um", "dois", "três", "quatro", "cinco", "seis", "sete", "oito", "nove"], 1);
+        let scanner = Scanner::new(&portuguese);
+        let line = "átrêsxx5éseteç";
+        let first = scanner.first(line).unwrap();
+        let last = scanner.last(line).unwrap();
+        assert_eq!(&line[first.start..first.end], "três");
+        assert_eq!(&line[last.start..last.end], "sete");
+        assert_eq!(scanner.line_value(line), 37);
+    }
+
+    #[test]
+    fn test_scanner_unicode_digits() {
+        let line = "x٣ab５y";
+        assert_eq!(Scanner::new(&Vocabulary::digits()).line_value(line), 0);
+
+        let scanner = Scanner::new(&Vocabulary::digits().with_unicode_digits());
+        assert_eq!(scanner.line_value(line), 35);
+        assert_eq!(scanner.line_value("१२३"), 13);
+        assert_eq!(scanner.line_value("๙"), 99);
+
+        let last = scanner.last(line).unwrap();
+        assert_eq!(&line[last.start..last.end], "５");
+    }
+
+    #[test]
+    fn test_line_report() {
+        let scanner = Scanner::new(&Vocabulary::english());
+        let report = scanner.report("two1nine\r\nxyz\n\n7pqrstsixteen");
+        assert_eq!(report.len(), 4);
+        assert_eq!(report[0], LineReport {
+            line: 1,
+            offset: 0,
+            first: Some(Token { value: 2, start: 0, end: 3 }),
+            last: Some(Token { value: 9, start: 4, end: 8 }),
+            value: 29,
+        });
+        assert_eq!(report[1].offset, 10);
+        assert_eq!(report[1].first, None);
+        assert_eq!(report[1].value, 0);
+        assert_eq!(report[3].offset, 15);
+        assert_eq!(report[3].last, Some(Token { value: 6, start: 6, end: 9 }));
+
+        let rejected: Vec<usize> = report.iter().filter(|line| line.value == 0).map(|line| line.line).collect();
+        assert_eq!(rejected, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_total_parallel() {
+        let lines = example_input(1, "spelled-digits") + "\n";
+        let input = lines.repeat(1000);
+        let scanner = Scanner::new(&Vocabulary::english());
+        assert_eq!(scanner.total(&input), 281000);
+        assert_eq!(scanner.total_parallel(&input, 4096), 281000);
+        assert_eq!(scanner.total_parallel(&input, 1), 281000);
+
+        let chunks = split_line_chunks(&input, 100);
+        assert_eq!(chunks.concat(), input);
+        assert!(chunks.iter().all(|chunk| chunk.ends_with('\n')));
+        assert_eq!(split_line_chunks("ab\ncd", 1), vec!["ab\n", "cd"]);
+    }
+
+    #[test]
+    fn test_scanner_vocabularies() {
+        let scanner = Scanner::new(&Vocabulary::english().with_token("zero", 0));
+        assert_eq!(scanner.line_value("zerone"), 1);
+        assert_eq!(scanner.line_value("4zero"), 40);
+
+        let spanish = Vocabulary::digits()
+            .with_words(&["uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve"], 1);
+        let scanner = Scanner::new(&spanish);
+        assert_eq!(scanner.total("dosiete\nxcuatrox\nnueveinte"), 27 + 44 + 99);
+
+        let scanner = Scanner::new(&Vocabulary::new().with_token("ten", 10).with_token("x", 3));
+        assert_eq!(scanner.line_value("xten"), 40);
+    }
 }
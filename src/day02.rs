@@ -1,199 +1,496 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{self, alpha1, space1},
+    combinator::all_consuming,
+    multi::separated_list1,
+    sequence::{delimited, separated_pair},
+    Finish,
+    IResult,
+    Parser,
+};
+
+use crate::checked;
+use crate::error::AocError;
+
+pub const RGB_COLORS: [&str; 3] = ["red", "green", "blue"];
+
+// Number of cubes per color. Colors that are not in the map have no cubes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct CubeSet {
-    pub red: u32,
-    pub green: u32,
-    pub blue: u32,
+    pub cubes: BTreeMap<String, u32>,
 }
 
-pub fn part1(input: &str, cube_set: CubeSet) -> u32 {
-    get_total_games(input, cube_set)
-}
+impl CubeSet {
+    pub fn new() -> Self {
+        CubeSet::default()
+    }
 
-pub fn part2(input: &str) -> u32 {
-    get_sum_of_power(input)
+    pub fn rgb(red: u32, green: u32, blue: u32) -> Self {
+        let mut cube_set = CubeSet::new();
+        cube_set.insert("red", red);
+        cube_set.insert("green", green);
+        cube_set.insert("blue", blue);
+        cube_set
+    }
 
-}
+    pub fn get(&self, color: &str) -> u32 {
+        self.cubes.get(color).copied().unwrap_or(0)
+    }
 
-fn get_total_games(input: &str, limits: CubeSet) -> u32 {
-    // Cubes is a tupple of red, green and blue cubes
-    // Value can be zero
-    let mut total: u32 = 0;
-    for line in input.lines() {
-        // Each line contains the game ID and the sets
-        // Number of sets may be arbitrary
-        if line.len() > 0 {
-            let game_info: Vec<&str> = line.split(": ").collect();
-            if let Some(game_id) = extract_game_id(game_info[0]) {
-                if game_info.get(1).is_some() {
-                    let sets_str = game_info[1].trim();
+    pub fn insert(&mut self, color: &str, count: u32) {
+        self.cubes.insert(color.to_string(), count);
+    }
 
-                    let cube_sets = extract_game_sets(sets_str);
-                    if within_limits(&limits, cube_sets) {
-                        total += game_id;
-                    }
-                }
-            }
+    // Whether every color in this set can be drawn from a bag with the given limits
+    pub fn within(&self, limits: &CubeSet) -> bool {
+        self.cubes.iter().all(|(color, count)| *count <= limits.get(color))
+    }
+
+    // Keeps the larger count of each color
+    pub fn merge_max(&mut self, other: &CubeSet) {
+        for (color, count) in other.cubes.iter() {
+            let current = self.cubes.entry(color.to_string()).or_insert(0);
+            *current = (*current).max(*count);
         }
     }
 
-    total
+    // Colors drawn zero times count as absent, the same as in find_smallest_bag
+    pub fn power(&self) -> Result<u64, AocError> {
+        let counts = self.cubes.values().filter(|count| **count > 0).map(|count| *count as u64);
+        checked::product(counts, "cube set power")
+    }
 }
 
-fn within_limits(limits: &CubeSet, sets: Vec<CubeSet>) -> bool {
-    let mut result: bool = true;
-    for set in sets.iter() {
-        if set.red > limits.red {
-            result = false;
-            break;
-        }
-        if set.green> limits.green {
-            result = false;
-            break;
-        }
-        if set.blue > limits.blue {
-            result = false;
-            break;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<CubeSet>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameError {
+    MalformedId(String),
+    MalformedRounds(String),
+    UnknownColor { game: u32, color: String },
+    DuplicateColor { game: u32, color: String },
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::MalformedId(line) => write!(f, "malformed game id: {}", line),
+            GameError::MalformedRounds(line) => write!(f, "malformed rounds: {}", line),
+            GameError::UnknownColor { game, color } => {
+                write!(f, "game {}: unknown color {}", game, color)
+            },
+            GameError::DuplicateColor { game, color } => {
+                write!(f, "game {}: color {} appears twice in a round", game, color)
+            },
         }
     }
+}
 
-    result
+impl std::error::Error for GameError {}
+
+// First round of an infeasible game that drew more of a color than the bag has
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Infeasibility {
+    pub game: u32,
+    pub round: usize,
+    pub color: String,
+    pub drawn: u32,
+    pub limit: u32,
 }
 
-fn extract_game_id(line: &str) -> Option<u32> {
-    let chunks: Vec<&str> = line.split(" ").collect();
-    if chunks.len() == 2 {
-        // The second chunk should be the game ID
-        if let Some(digits) = chunks.get(1) {
-            let digits_string = digits.to_string();
-            let game_id: u32 = digits_string.parse::<u32>().unwrap();
-            return Some(game_id);
-        }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BagCost {
+    TotalCubes,
+    Power,
+}
+
+// Canonical form lists colors in red, green, blue order, then any other colors
+// alphabetically, and leaves out the colors that were not drawn
+impl fmt::Display for CubeSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rgb = RGB_COLORS.iter().filter_map(|color| self.cubes.get_key_value(*color));
+        let others = self.cubes.iter().filter(|(color, _)| !RGB_COLORS.contains(&color.as_str()));
+        let drawn: Vec<String> = rgb.chain(others)
+            .filter(|(_, count)| **count > 0)
+            .map(|(color, count)| format!("{} {}", count, color))
+            .collect();
+        write!(f, "{}", drawn.join(", "))
     }
-    None
 }
 
-fn extract_game_sets(line: &str) -> Vec<CubeSet> {
-    let mut cube_sets: Vec<CubeSet> = Vec::new();
-    let set_lines: Vec<&str> = line.split("; ").collect();
-    for set_line in set_lines.iter() {
-        cube_sets.push(extract_game_set(set_line));
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rounds: Vec<String> = self.rounds.iter().map(|round| round.to_string()).collect();
+        write!(f, "Game {}: {}", self.id, rounds.join("; "))
     }
-    cube_sets 
 }
 
-fn extract_game_set(line: &str) -> CubeSet {
-    let mut cube_set = CubeSet {
-        red: 0,
-        green: 0,
-        blue: 0,
-    };
+pub fn part1(input: &str, cube_set: CubeSet) -> u64 {
+    let games = parse_games(input).expect("Games should be valid.");
+    get_total_games(&games, &cube_set)
+}
 
-    let color_lines: Vec<&str> = line.split(", ").collect();
-    for color_line in color_lines.iter() {
-        // First element is the value, second element is the color
-        let chunks: Vec<&str> = color_line.split(" ").collect();
-        let mut digit: u32 = 0;
-        if let Some(num_str) = chunks.get(0) {
-            let num_str_owned = num_str.to_string();
-            digit = num_str_owned.parse::<u32>().unwrap();
-        }
-        if let Some(color_str) = chunks.get(1) {
-            let color: &str = *color_str;
-            match color {
-                "red" => {
-                    cube_set.red = digit;
-                },
-                "green" => {
-                    cube_set.green = digit;
-                },
-                "blue" => {
-                    cube_set.blue = digit;
-                },
-                _ => {
-                    // Do nothing...
-                } 
-            }
-        }
-    }
+pub fn part2(input: &str) -> Result<u64, AocError> {
+    let games = parse_games(input).expect("Games should be valid.");
+    get_sum_of_power(&games)
+}
 
-    cube_set
+pub fn get_total_games(games: &[Game], limits: &CubeSet) -> u64 {
+    // Sum the IDs of the games where every round fits in the bag
+    games.iter()
+        .filter(|game| within_limits(limits, &game.rounds))
+        .map(|game| game.id as u64)
+        .sum()
 }
 
-fn get_sum_of_power(input: &str) -> u32 {
+fn within_limits(limits: &CubeSet, sets: &[CubeSet]) -> bool {
+    sets.iter().all(|set| set.within(limits))
+}
+
+pub fn get_sum_of_power(games: &[Game]) -> Result<u64, AocError> {
     // Get minimum cube set for each game and compute its power
     // Sum all the power
-    let mut total: u32 = 0;
-    for line in input.lines() {
-        // Each line contains the game ID and the sets
-        // Number of sets may be arbitrary
-        if line.len() > 0 {
-            let game_info: Vec<&str> = line.split(": ").collect();
-            if let Some(sets_str) = game_info.get(1) {
-                let cube_sets = extract_game_sets(sets_str);
-                let min_power = compute_min_power(cube_sets);
-                total += min_power; 
+    let powers = games.iter()
+        .map(|game| compute_min_set(&game.rounds).power())
+        .collect::<Result<Vec<u64>, AocError>>()?;
+    checked::sum(powers, "sum of powers")
+}
+
+pub fn compute_min_set(sets: &[CubeSet]) -> CubeSet {
+    let mut min_set = CubeSet::new();
+    for set in sets.iter() {
+        min_set.merge_max(set);
+    }
+    min_set
+}
+
+// Game IDs that are feasible under each of the bags, in the same order as the bags.
+// Each game is reduced to its minimum set once and then checked against every bag.
+pub fn feasible_games_per_bag(games: &[Game], bags: &[CubeSet]) -> Vec<Vec<u32>> {
+    let mut feasible: Vec<Vec<u32>> = vec![Vec::new(); bags.len()];
+    for game in games.iter() {
+        let min_set = compute_min_set(&game.rounds);
+        for (i, bag) in bags.iter().enumerate() {
+            if min_set.within(bag) {
+                feasible[i].push(game.id);
             }
         }
     }
+    feasible
+}
 
-    total
+pub fn feasibility_report(games: &[Game], limits: &CubeSet) -> Vec<Infeasibility> {
+    games.iter().filter_map(|game| {
+        game.rounds.iter().enumerate().find_map(|(round, set)| {
+            set.cubes.iter()
+                .find(|(color, count)| **count > limits.get(color))
+                .map(|(color, count)| Infeasibility {
+                    game: game.id,
+                    round,
+                    color: color.to_string(),
+                    drawn: *count,
+                    limit: limits.get(color),
+                })
+        })
+    }).collect()
 }
 
-fn compute_min_power(sets: Vec<CubeSet>) -> u32 {
-    let mut min_set = CubeSet {
-        red: 0,
-        green: 0,
-        blue: 0,
+// Smallest bag under which at least min_games games are feasible.
+// A bag admits a game when it covers the game's minimum set, so each color only
+// needs to be tried at the counts some game actually needs. Colors are assigned
+// one at a time, cutting branches that admit too few games or that cannot beat
+// the best bag found so far.
+pub fn find_smallest_bag(games: &[Game], min_games: usize, cost: BagCost) -> Option<CubeSet> {
+    if min_games > games.len() {
+        return None;
+    }
+
+    let min_sets: Vec<CubeSet> = games.iter().map(|game| compute_min_set(&game.rounds)).collect();
+    let mut colors: Vec<String> = min_sets.iter().flat_map(|set| set.cubes.keys().cloned()).collect();
+    colors.sort();
+    colors.dedup();
+
+    let needs: Vec<Vec<u32>> = min_sets.iter().map(|set| {
+        colors.iter().map(|color| set.get(color)).collect()
+    }).collect();
+
+    let search = BagSearch {
+        needs: &needs,
+        min_games,
+        cost,
     };
+    let admitted: Vec<usize> = (0..needs.len()).collect();
+    let mut chosen: Vec<u32> = Vec::new();
+    let mut best: Option<(u64, Vec<u32>)> = None;
+    search.search(&admitted, &mut chosen, &mut best);
 
-    for set in sets.iter() {
-        if set.red > min_set.red {
-            min_set.red = set.red;
+    best.map(|(_, counts)| {
+        let mut bag = CubeSet::new();
+        for (color, count) in colors.iter().zip(counts) {
+            if count > 0 {
+                bag.insert(color, count);
+            }
         }
-        if set.green > min_set.green {
-            min_set.green = set.green;
+        bag
+    })
+}
+
+struct BagSearch<'a> {
+    needs: &'a [Vec<u32>],
+    min_games: usize,
+    cost: BagCost,
+}
+
+impl BagSearch<'_> {
+    fn search(&self, admitted: &[usize], chosen: &mut Vec<u32>, best: &mut Option<(u64, Vec<u32>)>) {
+        if admitted.len() < self.min_games {
+            return;
         }
-        if set.blue > min_set.blue {
-            min_set.blue = set.blue;
+
+        // Any remaining color needs at least the count of the nth smallest admitted game
+        let colors = self.needs.first().map(|needs| needs.len()).unwrap_or(0);
+        let bounds: Vec<u32> = (chosen.len()..colors).map(|color| {
+            let mut counts: Vec<u32> = admitted.iter().map(|game| self.needs[*game][color]).collect();
+            counts.sort();
+            if self.min_games == 0 { 0 } else { counts[self.min_games - 1] }
+        }).collect();
+
+        let lower_bound = self.compute_cost(chosen.iter().chain(bounds.iter()));
+        if let Some((best_cost, _)) = best {
+            if lower_bound >= *best_cost {
+                return;
+            }
         }
+
+        if chosen.len() == colors {
+            *best = Some((lower_bound, chosen.clone()));
+            return;
+        }
+
+        let color = chosen.len();
+        let mut counts: Vec<u32> = admitted.iter().map(|game| self.needs[*game][color]).collect();
+        counts.sort();
+        counts.dedup();
+
+        for count in counts.into_iter().filter(|count| *count >= bounds[0]) {
+            let next: Vec<usize> = admitted.iter()
+                .filter(|game| self.needs[**game][color] <= count)
+                .copied()
+                .collect();
+            chosen.push(count);
+            self.search(&next, chosen, best);
+            chosen.pop();
+        }
+    }
+
+    fn compute_cost<'b>(&self, counts: impl Iterator<Item = &'b u32>) -> u64 {
+        match self.cost {
+            BagCost::TotalCubes => counts.map(|count| *count as u64).sum(),
+            BagCost::Power => counts.filter(|count| **count > 0).map(|count| *count as u64).product(),
+        }
+    }
+}
+
+pub fn parse_games(input: &str) -> Result<Vec<Game>, GameError> {
+    parse_games_with_colors(input, &RGB_COLORS)
+}
+
+pub fn parse_games_with_colors(input: &str, colors: &[&str]) -> Result<Vec<Game>, GameError> {
+    input.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| parse_game_with_colors(line, colors))
+        .collect()
+}
+
+pub fn parse_game(line: &str) -> Result<Game, GameError> {
+    parse_game_with_colors(line, &RGB_COLORS)
+}
+
+pub fn parse_game_with_colors(line: &str, colors: &[&str]) -> Result<Game, GameError> {
+    let (rest, id) = game_id(line)
+        .finish()
+        .map_err(|_| GameError::MalformedId(line.to_string()))?;
+
+    let (_, rounds) = all_consuming(separated_list1(tag("; "), round))(rest)
+        .finish()
+        .map_err(|_| GameError::MalformedRounds(line.to_string()))?;
+
+    let rounds = rounds.iter()
+        .map(|round| to_cube_set(id, round, colors))
+        .collect::<Result<Vec<CubeSet>, GameError>>()?;
+
+    Ok(Game { id, rounds })
+}
+
+fn game_id(line: &str) -> IResult<&str, u32> {
+    // Game 1: ...
+    delimited(tag("Game "), complete::u32, tag(": ")).parse(line)
+}
+
+fn round(line: &str) -> IResult<&str, Vec<(u32, &str)>> {
+    // 3 blue, 4 red
+    separated_list1(tag(", "), separated_pair(complete::u32, space1, alpha1)).parse(line)
+}
+
+fn to_cube_set(game: u32, round: &[(u32, &str)], colors: &[&str]) -> Result<CubeSet, GameError> {
+    let mut cube_set = CubeSet::new();
+
+    for (count, color) in round.iter() {
+        if !colors.contains(color) {
+            return Err(GameError::UnknownColor { game, color: color.to_string() });
+        }
+        if cube_set.cubes.contains_key(*color) {
+            return Err(GameError::DuplicateColor { game, color: color.to_string() });
+        }
+        cube_set.insert(color, *count);
     }
 
-    min_set.red * min_set.green * min_set.blue
+    Ok(cube_set)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::example_input;
+
+    crate::example_tests!(2);
+
+    #[test]
+    fn test_parse_game() {
+        let game = parse_game("Game 12: 3 blue, 4 red; 2 green").unwrap();
+        assert_eq!(game.id, 12);
+        assert_eq!(game.rounds.len(), 2);
+        assert_eq!(game.rounds[0].get("red"), 4);
+        assert_eq!(game.rounds[0].get("green"), 0);
+        assert_eq!(game.rounds[0].get("blue"), 3);
+        assert_eq!(game.rounds[1].cubes.len(), 1);
+
+        // Canonical lines come back out unchanged
+        let line = "Game 3: 20 red, 8 green, 6 blue; 4 red, 13 green, 5 blue; 1 red, 5 green";
+        assert_eq!(parse_game(line).unwrap().to_string(), line);
+        assert_eq!(game.to_string(), "Game 12: 4 red, 3 blue; 2 green");
+        assert_eq!(parse_game(game.to_string().as_str()).unwrap(), game);
+    }
+
+    #[test]
+    fn test_example_round_trip() {
+        // Example lines list colors in any order and come back in canonical order
+        let input = example_input(2, "games");
+        let original = input.lines().nth(2).unwrap();
+        assert_eq!(original, "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red");
+
+        let game = parse_game(original).unwrap();
+        let canonical = game.to_string();
+        assert_eq!(canonical, "Game 3: 20 red, 8 green, 6 blue; 4 red, 13 green, 5 blue; 1 red, 5 green");
+        assert_eq!(parse_game(&canonical).unwrap(), game);
+        assert_eq!(parse_game(&canonical).unwrap().to_string(), canonical);
+    }
 
     #[test]
-    fn test_day2_puzzle01() {
-        let input = r#"
-Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
-        "#;
-        let cube_set = CubeSet {
-            red: 12,
-            green: 13,
-            blue: 14,
-        };
-        let total = get_total_games(input, cube_set);
-        assert_eq!(total, 8);
+    fn test_parse_game_errors() {
+        assert_eq!(
+            parse_game("Game 1: 3 blue, 4 purple"),
+            Err(GameError::UnknownColor { game: 1, color: "purple".to_string() })
+        );
+        assert_eq!(
+            parse_game("Game 2: 3 blue; 1 red, 4 red"),
+            Err(GameError::DuplicateColor { game: 2, color: "red".to_string() })
+        );
+        assert_eq!(
+            parse_game("Game x: 3 blue"),
+            Err(GameError::MalformedId("Game x: 3 blue".to_string()))
+        );
+        assert_eq!(
+            parse_game("Game 4: 3 blue,, 1 red"),
+            Err(GameError::MalformedRounds("Game 4: 3 blue,, 1 red".to_string()))
+        );
+        assert!(parse_games("Game 1: 1 red\nGame 2 1 red").is_err());
     }
 
     #[test]
-    fn test_day2_puzzle02() {
-        let input = r#"
-Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
-        "#;
-        let total = get_sum_of_power(input);
-        assert_eq!(total, 2286);
+    fn test_feasible_games_per_bag() {
+        let input = example_input(2, "games");
+        let games = parse_games(&input).unwrap();
+        let bags = vec![CubeSet::rgb(12, 13, 14), CubeSet::rgb(20, 20, 20), CubeSet::rgb(1, 1, 1)];
+        assert_eq!(feasible_games_per_bag(&games, &bags), vec![
+            vec![1, 2, 5],
+            vec![1, 2, 3, 4, 5],
+            vec![],
+        ]);
+    }
 
+    #[test]
+    fn test_feasibility_report() {
+        let input = example_input(2, "games");
+        let games = parse_games(&input).unwrap();
+        let report = feasibility_report(&games, &CubeSet::rgb(12, 13, 14));
+        assert_eq!(report, vec![
+            Infeasibility { game: 3, round: 0, color: "red".to_string(), drawn: 20, limit: 12 },
+            Infeasibility { game: 4, round: 2, color: "blue".to_string(), drawn: 15, limit: 14 },
+        ]);
     }
 
+    #[test]
+    fn test_find_smallest_bag() {
+        let input = example_input(2, "games");
+        let games = parse_games(&input).unwrap();
+
+        let totals: Vec<u32> = (1..=5).map(|n| {
+            let bag = find_smallest_bag(&games, n, BagCost::TotalCubes).unwrap();
+            assert!(feasible_games_per_bag(&games, std::slice::from_ref(&bag))[0].len() >= n);
+            bag.cubes.values().sum()
+        }).collect();
+        assert_eq!(totals, vec![8, 13, 15, 32, 48]);
+
+        let powers: Vec<u64> = (1..=5).map(|n| {
+            find_smallest_bag(&games, n, BagCost::Power).unwrap().power().unwrap()
+        }).collect();
+        assert_eq!(powers, vec![12, 72, 108, 630, 3900]);
+
+        assert_eq!(find_smallest_bag(&games, 3, BagCost::Power), Some(CubeSet::rgb(6, 3, 6)));
+        assert_eq!(find_smallest_bag(&games, 6, BagCost::Power), None);
+    }
+
+    #[test]
+    fn test_custom_palette() {
+        let colors = ["red", "green", "blue", "yellow", "violet"];
+        let input = "Game 1: 3 yellow, 4 red; 2 violet, 1 yellow
+Game 2: 7 yellow; 1 green, 1 blue
+Game 3: 2 red, 2 violet";
+        let games = parse_games_with_colors(input, &colors).unwrap();
+        assert!(parse_games(input).is_err());
+
+        let mut limits = CubeSet::rgb(5, 5, 5);
+        limits.insert("yellow", 5);
+        assert_eq!(get_total_games(&games, &limits), 0);
+        limits.insert("violet", 2);
+        assert_eq!(get_total_games(&games, &limits), 4);
+
+        let min_set = compute_min_set(&games[0].rounds);
+        assert_eq!(min_set.to_string(), "4 red, 2 violet, 3 yellow");
+
+        // Only the colors each game actually drew count towards its power
+        assert_eq!(get_sum_of_power(&games), Ok(24 + 7 + 4));
+    }
+
+    #[test]
+    fn test_zero_counts() {
+        // A color drawn zero times is the same as a color not drawn at all
+        let games = parse_games("Game 1: 0 red, 2 blue; 3 green\nGame 2: 1 blue").unwrap();
+        let min_set = compute_min_set(&games[0].rounds);
+        assert_eq!(min_set.to_string(), "3 green, 2 blue");
+        assert_eq!(min_set.power(), Ok(6));
+        assert_eq!(get_sum_of_power(&games), Ok(6 + 1));
+
+        let bag = find_smallest_bag(&games, 2, BagCost::Power).unwrap();
+        assert_eq!(bag.to_string(), "3 green, 2 blue");
+        assert_eq!(bag.power(), min_set.power());
+    }
 }
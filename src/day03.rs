@@ -1,400 +1,493 @@
-use std::collections::BTreeSet;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use num::{BigUint, CheckedAdd, CheckedMul, One, Zero};
+use serde::Serialize;
+
+use crate::checked;
+use crate::error::AocError;
+use crate::grid::Grid;
+
+// What occupies each cell of the schematic. Numbers and symbols refer to
+// their index in the schematic's numbers and symbols lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    Number(usize),
+    Symbol(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineNumber {
+    pub position: (usize, usize),
+    pub length: usize,
+    pub value: u32,
+    // As written in the schematic, leading zeros included
+    pub digits: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EngineSymbol {
+    pub position: (usize, usize),
+    pub symbol: char,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderStyle {
+    Ansi,
+    Html,
+}
 
-#[derive(Clone)]
-pub struct PartSymbol {
+// One entry of the machine readable listing of numbers
+#[derive(Debug, Clone, Serialize)]
+pub struct NumberReport<'a> {
+    pub value: u32,
     pub position: (usize, usize),
     pub length: usize,
-    pub symbol: Option<char>,
-    pub number: Option<u32>,
-    pub is_number: bool,
+    pub counted: bool,
+    pub symbols: Vec<&'a EngineSymbol>,
+}
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_PART: &str = "\x1b[1;32m";
+const ANSI_IGNORED: &str = "\x1b[2;31m";
+const ANSI_GEAR: &str = "\x1b[1;33m";
+const ANSI_SYMBOL: &str = "\x1b[36m";
+
+const HTML_STYLE: &str = "<style>
+.schematic .part { color: #2e7d32; font-weight: bold; }
+.schematic .ignored { color: #c62828; }
+.schematic .gear { color: #f9a825; font-weight: bold; }
+.schematic .symbol { color: #00838f; }
+</style>";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartCount {
+    Exactly(usize),
+    AtLeast(usize),
 }
 
-pub fn part1(input: &str) -> u32 {
-    sum_part_numbers(input)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GearCombine {
+    Product,
+    Sum,
+    Max,
 }
 
-pub fn part2(input: &str) -> u32 {
-    compute_gears(input)
+// Any integer type gear ratios can be computed in, e.g. u64, u128 or BigUint
+pub trait GearRatio: From<u32> + Zero + One + Ord + CheckedAdd + CheckedMul + Display {}
+
+impl<T: From<u32> + Zero + One + Ord + CheckedAdd + CheckedMul + Display> GearRatio for T {}
+
+// Which symbols act as gears, how many parts they need and how the parts
+// are combined into a ratio
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearRule {
+    pub symbols: Vec<char>,
+    pub parts: PartCount,
+    pub combine: GearCombine,
 }
 
-fn sum_part_numbers(input: &str) -> u32 {
-    // Map coordinates of all special characters, excluding "."
-    // Find all adjacent numbers, but do not repeat number if already accounted for
-    // Sum all numbers
-    let mut symbols: Vec<PartSymbol> = Vec::new();
-    let mut rows: Vec<Vec<PartSymbol>> = Vec::new();
-    let mut counted: BTreeSet<(usize, usize)> = BTreeSet::new();
-    let mut total: u32 = 0;
-
-    for (i, line) in input.lines().enumerate() {
-        let line_symbols = parse_line_for_symbols(line, i);
-        for line_symbol in line_symbols.iter() {
-            symbols.push(line_symbol.clone());
+impl GearRule {
+    // A * touching exactly two parts, multiplied together
+    pub fn standard() -> Self {
+        GearRule {
+            symbols: vec!['*'],
+            parts: PartCount::Exactly(2),
+            combine: GearCombine::Product,
         }
+    }
 
-        rows.push(line_symbols);
+    fn matches(&self, symbol: char, parts: usize) -> bool {
+        let enough = match self.parts {
+            PartCount::Exactly(count) => parts == count,
+            PartCount::AtLeast(count) => parts >= count,
+        };
+        enough && self.symbols.contains(&symbol)
     }
 
-    let markers: Vec<&PartSymbol> = symbols.iter().filter(|x| !x.is_number).collect();
-
-    for marker in markers.iter() {
-        // Find all numbers adjacent to the symbol
-        let same_line_symbols = &rows[marker.position.0];
-        // Find symbols on same line
-        for symbol in same_line_symbols.iter() {
-            if symbol.is_number {
-                // Find symbols to the left
-                if (symbol.position.1 + symbol.length) == marker.position.1 {
-                    if !counted.contains(&symbol.position) {
-                        total += symbol.number.unwrap();
-                        counted.insert(symbol.position);
-                    }
-                }
+    pub fn combine<T: GearRatio>(&self, parts: &[&EngineNumber]) -> Result<T, AocError> {
+        let values = parts.iter().map(|part| T::from(part.value));
+        match self.combine {
+            GearCombine::Product => checked::product(values, "gear ratio"),
+            GearCombine::Sum => checked::sum(values, "gear ratio"),
+            GearCombine::Max => Ok(values.max().unwrap_or(T::zero())),
+        }
+    }
+}
 
-                // Find symbols to the right
-                if (symbol.position.1 > 0) && symbol.position.1 - 1 == marker.position.1 {
-                    if !counted.contains(&symbol.position) {
-                        total += symbol.number.unwrap();
-                        counted.insert(symbol.position);
+// Numbers and symbols form a bipartite graph where an edge means the symbol
+// touches the number, including diagonally
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    pub cells: Grid<Cell>,
+    pub numbers: Vec<EngineNumber>,
+    pub symbols: Vec<EngineSymbol>,
+    number_symbols: Vec<Vec<usize>>,
+    symbol_numbers: Vec<Vec<usize>>,
+}
+
+impl Schematic {
+    pub fn parse(input: &str) -> Self {
+        // Rows may be ragged, short ones are padded with empty cells
+        let lines: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+        let cols = lines.iter().map(|chars| chars.len()).max().unwrap_or(0);
+        let mut cells: Grid<Cell> = Grid::filled(lines.len(), cols, Cell::Empty);
+        let mut numbers: Vec<EngineNumber> = Vec::new();
+        let mut symbols: Vec<EngineSymbol> = Vec::new();
+
+        for (row, chars) in lines.iter().enumerate() {
+            let mut col: usize = 0;
+
+            while col < chars.len() {
+                let ch = chars[col];
+                if ch.is_ascii_digit() {
+                    // Consume the whole number and mark every cell it covers
+                    let start = col;
+                    while col < chars.len() && chars[col].is_ascii_digit() {
+                        cells[(row, col)] = Cell::Number(numbers.len());
+                        col += 1;
                     }
+                    let digits: String = chars[start..col].iter().collect();
+                    numbers.push(EngineNumber {
+                        position: (row, start),
+                        length: col - start,
+                        value: digits.parse::<u32>().expect("Number should fit in u32."),
+                        digits,
+                    });
+                    continue;
                 }
-            }
-        }
 
-        // Find symbols above
-        if marker.position.0 > 0 {
-            if let Some(above_symbols) = &rows.get(marker.position.0 - 1) {
-                for symbol in above_symbols.iter() {
-                    if symbol.is_number && symbol.length > 0 {
-                        if is_hitbox(marker.position.1, symbol.position.1, symbol.length) {
-                            if !counted.contains(&symbol.position) {
-                                total += symbol.number.unwrap();
-                                counted.insert(symbol.position);
-                            }
-                        }
-                    }
+                if ch != '.' {
+                    cells[(row, col)] = Cell::Symbol(symbols.len());
+                    symbols.push(EngineSymbol {
+                        position: (row, col),
+                        symbol: ch,
+                    });
                 }
+                col += 1;
             }
         }
 
-        // Find symbols below
-        if let Some(below_symbols) = &rows.get(marker.position.0 + 1) {
-            for symbol in below_symbols.iter() {
-                if symbol.is_number && symbol.length > 0 {
-                    if is_hitbox(marker.position.1, symbol.position.1, symbol.length) {
-                        if !counted.contains(&symbol.position) {
-                            total += symbol.number.unwrap();
-                            counted.insert(symbol.position);
-                        }
+        // Each number only looks at the ring of cells around it
+        let mut number_symbols: Vec<Vec<usize>> = vec![Vec::new(); numbers.len()];
+        let mut symbol_numbers: Vec<Vec<usize>> = vec![Vec::new(); symbols.len()];
+
+        for (id, number) in numbers.iter().enumerate() {
+            let (row, col) = number.position;
+            for r in row.saturating_sub(1)..=row + 1 {
+                for c in col.saturating_sub(1)..=col + number.length {
+                    if let Some(Cell::Symbol(symbol)) = cells.get((r, c)) {
+                        number_symbols[id].push(*symbol);
+                        symbol_numbers[*symbol].push(id);
                     }
                 }
             }
         }
-    }
-
-    total
-}
-
-fn parse_line_for_symbols(line: &str, line_number: usize) -> Vec<PartSymbol> {
-    let mut symbols: Vec<PartSymbol> = Vec::new();
-    let mut digits_buffer = String::new(); 
-    let mut last_index: usize = 0;
-
-    for (i, char) in line.chars().enumerate() {
-        last_index = i;
-        if char.is_numeric() {
-            // Found a digit, push it to buffer in case this is a part of a bigger number
-            digits_buffer.push(char);
-        } else if char == '.' {
-            // Flush digits buffer
-            if digits_buffer.len() > 0 {
-                if let Some(symbol) = convert_to_part_symbol(digits_buffer.as_str(), line_number, last_index) {
-                    symbols.push(symbol);
-                }
-            }
-            digits_buffer = String::new();
-        } else {
-            if digits_buffer.len() > 0 {
-                // Flush digits buffer
-                if let Some(symbol) = convert_to_part_symbol(digits_buffer.as_str(), line_number, last_index) {
-                    symbols.push(symbol);
-                }
-            }
-            digits_buffer = String::new();
-
-            // New symbol found, add to symbols
-            let symbol = PartSymbol {
-                position: (line_number, i),
-                length: 1,
-                number: None,
-                symbol: Some(char),
-                is_number: false,
-            };
 
-            symbols.push(symbol);
+        Schematic {
+            cells,
+            numbers,
+            symbols,
+            number_symbols,
+            symbol_numbers,
         }
     }
 
-    if digits_buffer.len() > 0 {
-        if let Some(symbol) = convert_to_part_symbol(digits_buffer.as_str(), line_number, last_index) {
-            symbols.push(symbol);
-        }
+    pub fn cell(&self, row: usize, col: usize) -> Cell {
+        self.cells.get((row, col)).copied().unwrap_or(Cell::Empty)
     }
-    symbols
-}
 
-fn convert_to_part_symbol(digits_buffer: &str, line_number: usize, last_index: usize) -> Option<PartSymbol> {
-    if let Ok(number) = digits_buffer.parse::<u32>() {
-        let mut y: usize = 0;
-        if last_index >= digits_buffer.len() {
-            y = last_index - digits_buffer.len();
-        }
-        let symbol = PartSymbol {
-            position: (line_number, y),
-            length: digits_buffer.len(),
-            number: Some(number),
-            symbol: None,
-            is_number: true,
-        };
-        return Some(symbol);
+    pub fn symbols_of(&self, number: usize) -> &[usize] {
+        &self.number_symbols[number]
     }
 
-    None
-}
-
-fn is_hitbox_edges(col: usize, index: usize, length: usize) -> bool {
-    // Start is exact match? 
-    if col == index {
-        return true;
+    pub fn numbers_of(&self, symbol: usize) -> &[usize] {
+        &self.symbol_numbers[symbol]
     }
 
-    // End is at exact position?
-    if (index + length - 1) == col {
-        return true;
+    // Numbers touching at least one symbol
+    pub fn part_numbers(&self) -> Vec<&EngineNumber> {
+        self.numbers.iter().enumerate()
+            .filter(|(id, _)| !self.number_symbols[*id].is_empty())
+            .map(|(_, number)| number)
+            .collect()
     }
 
-    // End is diagonal left?
-    if (index + length) == col {
-        return true;
+    // Symbols that count as gears under the rule, along with their parts
+    pub fn gears(&self, rule: &GearRule) -> Vec<(&EngineSymbol, Vec<&EngineNumber>)> {
+        self.symbols.iter().enumerate()
+            .filter(|(id, symbol)| rule.matches(symbol.symbol, self.symbol_numbers[*id].len()))
+            .map(|(id, symbol)| (symbol, self.symbol_numbers[id].iter().map(|n| &self.numbers[*n]).collect()))
+            .collect()
     }
 
-    // Start is diagonal right?
-    if index > 0 && (index - 1) == col {
-        return true;
+    pub fn gear_ratios<T: GearRatio>(&self, rule: &GearRule) -> Result<Vec<(&EngineSymbol, T)>, AocError> {
+        self.gears(rule).into_iter()
+            .map(|(symbol, parts)| rule.combine(&parts).map(|ratio| (symbol, ratio)))
+            .collect()
     }
 
-    return false;
-}
-
-fn is_hitbox_middle(col: usize, index: usize, length: usize) -> bool {
-    // Should cover the following:
-    // index aligned with col
-    // index diagonal to the right
-    // end of symbol is aligned with col
-    // end of symbol is diagonal to the left
-    // col hits the body of the symbol
-    let mut start = index;
-    if index >= length {
-        start -= 1;
+    pub fn sum_gear_ratios<T: GearRatio>(&self, rule: &GearRule) -> Result<T, AocError> {
+        let ratios = self.gear_ratios(rule)?.into_iter().map(|(_, ratio)| ratio);
+        checked::sum(ratios, "sum of gear ratios")
     }
-    let end = start + length;
-    col >= start && col <= end
-}
 
-fn is_hitbox(col: usize, index: usize, length: usize) -> bool {
-    if is_hitbox_middle(col, index, length) {
-        return true;
+    pub fn numbers_touching(&self, symbol: char) -> Vec<&EngineNumber> {
+        self.numbers.iter().enumerate()
+            .filter(|(id, _)| self.number_symbols[*id].iter().any(|s| self.symbols[*s].symbol == symbol))
+            .map(|(_, number)| number)
+            .collect()
     }
-    return is_hitbox_edges(col, index, length);
-}
-
-fn compute_gears(input: &str) -> u32 {
-    // Gears are two parts that are adjacent to each other via the * symbol
-    // Map coordinates of all special characters, excluding "."
-    // Find all adjacent numbers, but do not repeat number if already accounted for
-    // Sum all numbers
-    let mut symbols: Vec<PartSymbol> = Vec::new();
-    let mut rows: Vec<Vec<PartSymbol>> = Vec::new();
-    let mut total: u32 = 0;
-
-    for (i, line) in input.lines().enumerate() {
-        let line_symbols = parse_line_for_symbols(line, i);
-        for line_symbol in line_symbols.iter() {
-            symbols.push(line_symbol.clone());
-        }
 
-        rows.push(line_symbols);
+    pub fn isolated_symbols(&self) -> Vec<&EngineSymbol> {
+        self.symbols.iter().enumerate()
+            .filter(|(id, _)| self.symbol_numbers[*id].is_empty())
+            .map(|(_, symbol)| symbol)
+            .collect()
     }
 
-    let mut gear_symbols: Vec<&PartSymbol> = Vec::new(); 
-
-    for symbol in symbols.iter() {
-        if !symbol.is_number && symbol.symbol.unwrap() == '*' {
-           gear_symbols.push(symbol);
-        }
+    // Every number with whether it counts as a part and the symbols it touches
+    pub fn number_reports(&self) -> Vec<NumberReport<'_>> {
+        self.numbers.iter().enumerate().map(|(id, number)| NumberReport {
+            value: number.value,
+            position: number.position,
+            length: number.length,
+            counted: !self.number_symbols[id].is_empty(),
+            symbols: self.number_symbols[id].iter().map(|s| &self.symbols[*s]).collect(),
+        }).collect()
     }
 
-    for gear in gear_symbols.clone().iter() {
-        let gear_ratio = find_gear_ratio(gear, &rows);
-        total += gear_ratio;
+    pub fn number_reports_json(&self) -> String {
+        serde_json::to_string_pretty(&self.number_reports()).expect("Reports should serialize.")
     }
 
-    total
-}
-
-fn find_gear_ratio(gear: &PartSymbol, rows: &Vec<Vec<PartSymbol>>) -> u32 {
-    let mut parts: Vec<&PartSymbol> = Vec::new();
-    // Find all numbers adjacent to the symbol
-    let same_line_symbols = &rows[gear.position.0];
-    // Find symbols on same line
-    for symbol in same_line_symbols.iter() {
-        if symbol.is_number {
-            // Find symbols to the left
-            if (symbol.position.1 + symbol.length) == gear.position.1 {
-                parts.push(symbol);
+    // Reprints the schematic with counted part numbers, ignored numbers, gears
+    // and other symbols highlighted. Gear ratios are listed after their row.
+    pub fn render(&self, style: RenderStyle, rule: &GearRule) -> String {
+        let ratios: BTreeMap<(usize, usize), BigUint> = self.gear_ratios(rule)
+            .expect("BigUint ratios should not overflow.")
+            .into_iter()
+            .map(|(symbol, ratio)| (symbol.position, ratio))
+            .collect();
+
+        let mut lines: Vec<String> = Vec::new();
+        for (row, cells) in self.cells.iter_rows().enumerate() {
+            let mut line = String::new();
+            let mut col: usize = 0;
+
+            while col < cells.len() {
+                match cells[col] {
+                    Cell::Empty => {
+                        line.push('.');
+                        col += 1;
+                    },
+                    Cell::Number(id) => {
+                        let number = &self.numbers[id];
+                        let class = match self.number_symbols[id].is_empty() {
+                            true => "ignored",
+                            false => "part",
+                        };
+                        line.push_str(&highlight(style, class, &number.digits, None));
+                        col += number.length;
+                    },
+                    Cell::Symbol(id) => {
+                        let symbol = &self.symbols[id];
+                        let text = symbol.symbol.to_string();
+                        let highlighted = match ratios.get(&symbol.position) {
+                            Some(ratio) => highlight(style, "gear", &text, Some(ratio)),
+                            None => highlight(style, "symbol", &text, None),
+                        };
+                        line.push_str(&highlighted);
+                        col += 1;
+                    },
+                }
             }
 
-            // Find symbols to the right
-            if (symbol.position.1 > 0) && symbol.position.1 - 1 == gear.position.1 {
-                parts.push(symbol);
+            // ANSI has nowhere to hover, so spell out the ratios at the end of the row
+            if style == RenderStyle::Ansi {
+                let row_ratios: Vec<String> = ratios.range((row, 0)..(row + 1, 0))
+                    .map(|((_, c), ratio)| format!("{}={}", c, ratio))
+                    .collect();
+                if !row_ratios.is_empty() {
+                    line.push_str(&format!("  gears: {}", row_ratios.join(", ")));
+                }
             }
+
+            lines.push(line);
         }
-    }
 
-    // Find symbols above
-    if gear.position.0 > 0 {
-        if let Some(above_symbols) = &rows.get(gear.position.0 - 1) {
-            for symbol in above_symbols.iter() {
-                if symbol.is_number && symbol.length > 0 {
-                    if is_hitbox(gear.position.1, symbol.position.1, symbol.length) {
-                        parts.push(symbol);
-                    }
-                }
-            }
+        match style {
+            RenderStyle::Ansi => lines.join("\n"),
+            RenderStyle::Html => format!("{}\n<pre class=\"schematic\">\n{}\n</pre>", HTML_STYLE, lines.join("\n")),
         }
     }
 
-    // Find symbols below
-    if let Some(below_symbols) = &rows.get(gear.position.0 + 1) {
-        for symbol in below_symbols.iter() {
-            if symbol.is_number && symbol.length > 0 {
-                if is_hitbox(gear.position.1, symbol.position.1, symbol.length) {
-                    parts.push(symbol);
-                }
-            }
-        }
+    pub fn isolated_numbers(&self) -> Vec<&EngineNumber> {
+        self.numbers.iter().enumerate()
+            .filter(|(id, _)| self.number_symbols[*id].is_empty())
+            .map(|(_, number)| number)
+            .collect()
     }
+}
 
-    if parts.len() == 2 {
-        return parts[0].number.unwrap() * parts[1].number.unwrap();
+fn highlight(style: RenderStyle, class: &str, text: &str, ratio: Option<&BigUint>) -> String {
+    match style {
+        RenderStyle::Ansi => {
+            let color = match class {
+                "part" => ANSI_PART,
+                "ignored" => ANSI_IGNORED,
+                "gear" => ANSI_GEAR,
+                _ => ANSI_SYMBOL,
+            };
+            format!("{}{}{}", color, text, ANSI_RESET)
+        },
+        RenderStyle::Html => {
+            let text = text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+            match ratio {
+                Some(ratio) => format!("<span class=\"{}\" title=\"ratio {}\">{}</span>", class, ratio, text),
+                None => format!("<span class=\"{}\">{}</span>", class, text),
+            }
+        },
     }
-    return 0;
+}
+
+pub fn part1(input: &str) -> u64 {
+    sum_part_numbers(&Schematic::parse(input))
+}
+
+pub fn part2(input: &str) -> Result<u64, AocError> {
+    compute_gears(&Schematic::parse(input))
+}
+
+pub fn sum_part_numbers(schematic: &Schematic) -> u64 {
+    // Numbers adjacent to any symbol, each counted once
+    schematic.part_numbers().iter().map(|number| number.value as u64).sum()
+}
+
+pub fn compute_gears(schematic: &Schematic) -> Result<u64, AocError> {
+    // Gears are two parts that are adjacent to each other via the * symbol
+    schematic.sum_gear_ratios(&GearRule::standard())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::example_input;
 
-    #[test]
-    fn test_day3_puzzle01_data1() {
-        let input = r#"467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598.."#;
-        let total = sum_part_numbers(input);
-        assert_eq!(total, 4361);
-    }
+    crate::example_tests!(3);
 
     #[test]
-    fn test_day3_puzzle01_data2() {
-        let input = "12.......*..
-+.........34
-.......-12..
-..78........
-..*....60...
-78..........
-.......23...
-....90*12...
-............
-2.2......12.
-.*.........*
-1.1.......56";
-        let total = sum_part_numbers(input);
-        assert_eq!(total, 413);
+    fn test_schematic_graph() {
+        let input = example_input(3, "schematic");
+        let schematic = Schematic::parse(&input);
+        assert_eq!(schematic.numbers.len(), 10);
+        assert_eq!(schematic.symbols.len(), 6);
+        assert_eq!(schematic.cell(0, 2), Cell::Number(0));
+        assert_eq!(schematic.cell(1, 3), Cell::Symbol(0));
+        assert_eq!(schematic.cell(1, 4), Cell::Empty);
+        assert_eq!(schematic.numbers_of(0), &[0, 2]);
+        assert_eq!(schematic.symbols_of(3), &[1]);
+
+        let touching: Vec<u32> = schematic.numbers_touching('#').iter().map(|n| n.value).collect();
+        assert_eq!(touching, vec![633]);
+
+        let isolated: Vec<u32> = schematic.isolated_numbers().iter().map(|n| n.value).collect();
+        assert_eq!(isolated, vec![114, 58]);
+        assert!(schematic.isolated_symbols().is_empty());
+
+        let gears: Vec<u64> = schematic.gear_ratios(&GearRule::standard()).unwrap().iter().map(|(_, ratio)| *ratio).collect();
+        assert_eq!(gears, vec![16345, 451490]);
+
+        let lonely = Schematic::parse("1....\n...$.");
+        assert_eq!(lonely.isolated_symbols(), vec![&EngineSymbol { position: (1, 3), symbol: '$' }]);
+
+        let ragged = Schematic::parse("467..\n...*..\n35");
+        assert_eq!((ragged.cells.rows(), ragged.cells.cols()), (3, 6));
+        assert_eq!(ragged.cell(2, 1), Cell::Number(1));
+        assert_eq!(ragged.cell(2, 4), Cell::Empty);
+        let parts: Vec<u32> = ragged.numbers_touching('*').iter().map(|n| n.value).collect();
+        assert_eq!(parts, vec![467]);
     }
 
     #[test]
-    fn test_day3_puzzle01_data3() {
-        let input = "12.......*..
-+.........34
-.......-12..
-..78........
-..*....60...
-78.........9
-.5.....23..$
-8...90*12...
-............
-2.2......12.
-.*.........*
-1.1..503+.56";
-        let total = sum_part_numbers(input);
-        assert_eq!(total, 925);
-    }
-    
-    #[test]
-    fn test_day3_puzzle01_data4() {
-        let input = ".......5......
-..7*..*.......
-...*13*.......
-.......15.....";
-        let total = sum_part_numbers(input);
-        assert_eq!(total, 40);
+    fn test_render() {
+        let input = "467..114..
+...*......
+..35..633.
+......#...";
+        let schematic = Schematic::parse(input);
+        let ansi = schematic.render(RenderStyle::Ansi, &GearRule::standard());
+        let lines: Vec<&str> = ansi.lines().collect();
+        assert_eq!(lines[0], "\x1b[1;32m467\x1b[0m..\x1b[2;31m114\x1b[0m..");
+        assert_eq!(lines[1], "...\x1b[1;33m*\x1b[0m......  gears: 3=16345");
+
+        let html = Schematic::parse("1&<..\n....2").render(RenderStyle::Html, &GearRule::standard());
+        assert!(html.contains("<span class=\"part\">1</span><span class=\"symbol\">&amp;</span><span class=\"symbol\">&lt;</span>..\n....<span class=\"ignored\">2</span>"));
+
+        let html = schematic.render(RenderStyle::Html, &GearRule::standard());
+        assert!(html.contains("<span class=\"gear\" title=\"ratio 16345\">*</span>"));
+
+        // Leading zeros are kept so later columns stay in place
+        let padded = Schematic::parse("007..1\n.....#").render(RenderStyle::Ansi, &GearRule::standard());
+        assert_eq!(padded.lines().next(), Some("\x1b[2;31m007\x1b[0m..\x1b[1;32m1\x1b[0m"));
     }
 
     #[test]
-    fn test_day3_puzzle01_data5() {
-        let input = "100
-200";
-        let total = sum_part_numbers(input);
-        assert_eq!(total, 0);
+    fn test_number_reports_json() {
+        let schematic = Schematic::parse("467..\n...*.\n.....\n..12.");
+        let json: serde_json::Value = serde_json::from_str(&schematic.number_reports_json()).unwrap();
+        assert_eq!(json, serde_json::json!([
+            {
+                "value": 467,
+                "position": [0, 0],
+                "length": 3,
+                "counted": true,
+                "symbols": [{ "position": [1, 3], "symbol": "*" }],
+            },
+            {
+                "value": 12,
+                "position": [3, 2],
+                "length": 2,
+                "counted": false,
+                "symbols": [],
+            },
+        ]));
     }
 
     #[test]
-    fn test_day3_puzzle01_data6() {
-        let input = "503+";
-        let total = sum_part_numbers(input);
-        assert_eq!(total, 503);
-    }
+    fn test_gear_rules() {
+        let input = "100.200.300
+...*...+...
+99999.99999
+......&....
+...70000...";
+        let schematic = Schematic::parse(input);
+        assert_eq!(schematic.sum_gear_ratios::<u64>(&GearRule::standard()), Ok(0));
+
+        let rule = GearRule {
+            symbols: vec!['*', '+'],
+            parts: PartCount::AtLeast(3),
+            combine: GearCombine::Product,
+        };
+        // Too big for a u32
+        let ratios: Vec<u128> = schematic.gear_ratios(&rule).unwrap().iter().map(|(_, ratio)| *ratio).collect();
+        assert_eq!(ratios, vec![100 * 200 * 99999, 200 * 300 * 99999]);
+        if checked::ENABLED {
+            assert!(matches!(schematic.sum_gear_ratios::<u32>(&rule), Err(AocError::Overflow { .. })));
+        }
 
-    #[test]
-    fn test_day3_puzzle01_data7() {
-        let input = "............
-..789.......
-...+........
-............";
-        let total = sum_part_numbers(input);
-        assert_eq!(total, 789);
-    }
+        let rule = GearRule {
+            symbols: vec!['&'],
+            parts: PartCount::Exactly(2),
+            combine: GearCombine::Sum,
+        };
+        assert_eq!(schematic.sum_gear_ratios::<u64>(&rule), Ok(99999 + 70000));
 
-    #[test]
-    fn test_day3_puzzle02() {
-        let input = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
-        let gears = compute_gears(input);
-        assert_eq!(gears, 467835);
+        let rule = GearRule {
+            symbols: vec!['*', '+', '&'],
+            parts: PartCount::AtLeast(1),
+            combine: GearCombine::Max,
+        };
+        assert_eq!(schematic.sum_gear_ratios::<u64>(&rule), Ok(99999 * 3));
     }
 }
@@ -1,154 +1,348 @@
+use std::fmt::{self, Display};
+use num::{BigUint, CheckedAdd, CheckedSub, One, Zero};
+use serde::{Serialize, Serializer};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{self, space0, space1},
+    combinator::all_consuming,
+    multi::separated_list1,
+    sequence::{delimited, preceded, separated_pair, terminated},
+    Finish,
+    IResult,
+    Parser,
+};
+
+use crate::checked;
+use crate::error::AocError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Card {
-    pub matches: u32,
+    pub id: u32,
+    pub winning: Vec<u32>,
+    pub have: Vec<u32>,
 }
 
-pub fn part1(input: &str) -> u32 {
-    compute_winning_points(input)
+impl Card {
+    pub fn matches(&self) -> u32 {
+        self.matched_numbers().len() as u32
+    }
+
+    pub fn matched_numbers(&self) -> Vec<u32> {
+        self.have.iter().filter(|n| self.winning.contains(n)).copied().collect()
+    }
 }
 
-pub fn part2(input: &str) -> u32 {
-    compute_total_cards(input)
+// Copies a card got from one earlier card. Copy counts are written to JSON as
+// strings, they easily outgrow what JSON numbers can hold exactly.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(bound(serialize = "T: Display"))]
+pub struct CardContribution<T> {
+    pub card: u32,
+    #[serde(serialize_with = "serialize_count")]
+    pub copies: T,
 }
 
-fn compute_winning_points(input: &str) -> u32 {
-    let mut total: u32 = 0;
-    for line in input.lines() {
-        let card_info: Vec<&str> = line.split(":").collect();
-        let numbers: Vec<&str> = card_info[1].split(" |").collect();
-        let winners = collect_card_numbers(numbers[0]);
-        let numbers = collect_card_numbers(numbers[1]);
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(bound(serialize = "T: Display"))]
+pub struct CardAudit<T> {
+    pub id: u32,
+    pub matched: Vec<u32>,
+    #[serde(serialize_with = "serialize_count")]
+    pub points: T,
+    #[serde(serialize_with = "serialize_count")]
+    pub copies: T,
+    pub contributors: Vec<CardContribution<T>>,
+}
 
-        // Find matches
-        let matches: u32 = get_matching_number_count(&winners, &numbers);
-        let points = compute_card_points(matches);
-        total += points;
-    }
-    total
+fn serialize_count<T: Display, S: Serializer>(count: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(count)
 }
 
-fn get_matching_number_count(winners: &Vec<u32>, numbers: &Vec<u32>) -> u32 {
-    let matches: u32 = numbers.iter().map(|n| {
-        match winners.contains(n) {
-            true => 1,
-            false => 0,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardError {
+    Malformed(String),
+    DuplicateNumber { card: u32, number: u32 },
+}
+
+impl fmt::Display for CardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CardError::Malformed(line) => write!(f, "malformed card: {}", line),
+            CardError::DuplicateNumber { card, number } => {
+                write!(f, "card {}: number {} appears twice", card, number)
+            },
         }
-    }).sum();
-    matches
+    }
 }
 
-fn collect_card_numbers(line: &str) -> Vec<u32> {
-    let mut numbers: Vec<u32> = Vec::new();
-    let mut start: usize = 0;
-    while start < line.len() {
-        let num_str = &line[start..start + 3].trim().to_string();
-        let num: u32 = num_str.parse::<u32>().unwrap();
-        numbers.push(num);
+impl std::error::Error for CardError {}
 
-        // Next 3 chars
-        start += 3;
-    }
-    numbers
+// Any unsigned type that can hold card counts: u64, u128 or BigUint
+pub trait CardCount: Clone + Zero + One + CheckedAdd + CheckedSub + Display {}
+
+impl<T: Clone + Zero + One + CheckedAdd + CheckedSub + Display> CardCount for T {}
+
+pub fn part1(input: &str) -> Result<u64, AocError> {
+    let cards = parse_cards(input).expect("Cards should be valid.");
+    compute_winning_points(&cards)
+}
+
+pub fn part2(input: &str) -> Result<BigUint, AocError> {
+    let cards = parse_cards(input).expect("Cards should be valid.");
+    count_total_cards(&cards)
 }
 
-fn compute_card_points(matches: u32) -> u32 {
-    let mut value: u32 = 0;
+pub fn compute_winning_points(cards: &[Card]) -> Result<u64, AocError> {
+    let points = cards.iter()
+        .map(|card| compute_card_points::<u64>(card.matches()))
+        .collect::<Result<Vec<u64>, AocError>>()?;
+    checked::sum(points, "winning points")
+}
+
+fn compute_card_points<T: CardCount>(matches: u32) -> Result<T, AocError> {
+    let mut value = T::zero();
     if matches > 0 {
-        value = 1;
+        value = T::one();
         for _ in 0..matches - 1 {
-            value *= 2;
+            value = checked::add(value.clone(), value, "card points")?;
         }
     }
 
-    value
+    Ok(value)
 }
 
-fn compute_total_cards(input: &str) -> u32 {
-    let mut initial_cards: Vec<Card> = Vec::new();
-    let mut card_copies: Vec<u32> = Vec::new();
+pub fn count_total_cards<T: CardCount>(cards: &[Card]) -> Result<T, AocError> {
+    checked::sum(count_card_copies::<T>(cards)?, "total cards")
+}
 
-    // Collect original cards first
-    for line in input.lines() {
-        let card_info: Vec<&str> = line.split(":").collect();
-        let numbers: Vec<&str> = card_info[1].split(" |").collect();
-        let winners = collect_card_numbers(numbers[0]);
-        let numbers = collect_card_numbers(numbers[1]);
+// Number of copies of each card, including the original.
+// Every copy of a card wins one copy of each of the next cards, so all of its
+// copies are handed out in one step. A running total of the copies still being
+// handed out, dropped again where each win range ends, keeps this linear.
+pub fn count_card_copies<T: CardCount>(cards: &[Card]) -> Result<Vec<T>, AocError> {
+    let mut card_copies: Vec<T> = Vec::with_capacity(cards.len());
+    let mut running = T::zero();
+    let mut expiring: Vec<T> = vec![T::zero(); cards.len() + 1];
 
-        let card = Card {
-            matches: get_matching_number_count(&winners, &numbers),
-        };
+    for (i, card) in cards.iter().enumerate() {
+        running = checked::sub(running, expiring[i].clone(), "copies being handed out")?;
+        let copies = checked::add(T::one(), running.clone(), "card copies")?;
 
-        initial_cards.push(card);
-        card_copies.push(1);
+        // Ensure we don't get pass the bottom of the card list
+        let end = (i + 1 + card.matches() as usize).min(cards.len());
+        if end > i + 1 {
+            running = checked::add(running, copies.clone(), "copies being handed out")?;
+            expiring[end] = checked::add(expiring[end].clone(), copies.clone(), "copies being handed out")?;
+        }
+
+        card_copies.push(copies);
     }
 
-    // Expand the cards
-    for (i, card) in initial_cards.iter().enumerate() {
-        let copies = card_copies[i];
-        for _ in 0..copies {
-            // Add more card copies below the layer
-            // Create copies of cards down based on matches
-            if card.matches > 0 {
-                for i_match in 0..card.matches {
-                    let copy_index = i + i_match as usize + 1;
-                    // Ensure we don't get pass the bottom of the card list
-                    if initial_cards.get(copy_index).is_some() {
-                        card_copies[copy_index] += 1;
-                    }
-                }
+    Ok(card_copies)
+}
+
+// Blank lines are skipped, and so is any whitespace around the numbers,
+// which also takes care of CRLF line endings
+pub fn parse_cards(input: &str) -> Result<Vec<Card>, CardError> {
+    input.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(parse_card)
+        .collect()
+}
+
+pub fn parse_card(line: &str) -> Result<Card, CardError> {
+    let (_, card) = all_consuming(card)(line.trim())
+        .finish()
+        .map_err(|_| CardError::Malformed(line.to_string()))?;
+
+    for numbers in [&card.winning, &card.have] {
+        for (i, number) in numbers.iter().enumerate() {
+            if numbers[..i].contains(number) {
+                return Err(CardError::DuplicateNumber { card: card.id, number: *number });
             }
         }
     }
 
-    // Sum all cards
-    let total_cards: u32 = card_copies.iter().sum();
-    total_cards
+    Ok(card)
+}
+
+fn card(line: &str) -> IResult<&str, Card> {
+    // Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+    let id = delimited(tag("Card").and(space1), complete::u32, tag(":"));
+    let numbers = || delimited(space0, separated_list1(space1, complete::u32), space0);
+    let sides = separated_pair(numbers(), tag("|"), numbers());
+    preceded(space0, id.and(terminated(sides, space0)))
+        .map(|(id, (winning, have))| Card { id, winning, have })
+        .parse(line)
+}
+
+// Everything that went into each card's score and copy count
+pub fn audit_cards<T: CardCount>(cards: &[Card]) -> Result<Vec<CardAudit<T>>, AocError> {
+    let card_copies: Vec<T> = count_card_copies(cards)?;
+    let mut contributors: Vec<Vec<CardContribution<T>>> = vec![Vec::new(); cards.len()];
+
+    for (i, card) in cards.iter().enumerate() {
+        let end = (i + 1 + card.matches() as usize).min(cards.len());
+        for receivers in contributors[i + 1..end].iter_mut() {
+            receivers.push(CardContribution {
+                card: card.id,
+                copies: card_copies[i].clone(),
+            });
+        }
+    }
+
+    cards.iter().zip(card_copies).zip(contributors).map(|((card, copies), contributors)| {
+        Ok(CardAudit {
+            id: card.id,
+            matched: card.matched_numbers(),
+            points: compute_card_points(card.matches())?,
+            copies,
+            contributors,
+        })
+    }).collect()
+}
+
+pub fn audit_table<T: CardCount>(audits: &[CardAudit<T>]) -> String {
+    let mut lines: Vec<String> = vec![
+        format!("{:>6} | {:<30} | {:>6} | {:>12} | {}", "Card", "Matched", "Points", "Copies", "From"),
+    ];
+    for audit in audits.iter() {
+        let matched: Vec<String> = audit.matched.iter().map(|n| n.to_string()).collect();
+        let from: Vec<String> = audit.contributors.iter()
+            .map(|c| format!("{} ({})", c.card, c.copies))
+            .collect();
+        lines.push(format!(
+            "{:>6} | {:<30} | {:>6} | {:>12} | {}",
+            audit.id,
+            matched.join(" "),
+            audit.points,
+            audit.copies,
+            from.join(", "),
+        ).trim_end().to_string());
+    }
+    lines.join("\n")
+}
+
+pub fn audit_json<T: CardCount>(audits: &[CardAudit<T>]) -> String {
+    serde_json::to_string_pretty(audits).expect("Audits should serialize.")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::example_input;
+    use num::BigUint;
+
+    crate::example_tests!(4);
 
     #[test]
-    fn test_day4_puzzle01_data1() {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        let total = compute_winning_points(input);
-        assert_eq!(total, 13);
+    fn test_card_points() {
+        assert_eq!(compute_card_points::<u64>(4), Ok(8));
+        assert_eq!(compute_card_points::<u64>(2), Ok(2));
+        assert_eq!(compute_card_points::<u64>(1), Ok(1));
+        assert_eq!(compute_card_points::<u64>(0), Ok(0));
     }
 
     #[test]
-    fn test_day4_puzzle01_data2() {
-        let input = "Card  1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card  2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card  3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card  4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card  5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 16: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        let total = compute_winning_points(input);
-        assert_eq!(total, 13);
+    fn test_card_copies() {
+        let input = example_input(4, "scratchcards");
+        let cards = parse_cards(&input).unwrap();
+        assert_eq!(count_card_copies::<u64>(&cards), Ok(vec![1, 2, 4, 8, 14, 1]));
+    }
+
+    // Every card wins all the cards after it, doubling the copies each time
+    fn cascading_cards(size: u32) -> Vec<Card> {
+        (0..size).map(|i| {
+            let numbers: Vec<u32> = (i + 1..size).collect();
+            Card { id: i + 1, winning: numbers.clone(), have: numbers }
+        }).collect()
     }
 
     #[test]
-    fn test_card_points() {
-        assert_eq!(compute_card_points(4), 8);
-        assert_eq!(compute_card_points(2), 2);
-        assert_eq!(compute_card_points(1), 1);
-        assert_eq!(compute_card_points(0), 0);
+    fn test_total_cards_big() {
+        let size: u32 = 200;
+        let cards = cascading_cards(size);
+
+        let total: BigUint = count_total_cards(&cards).unwrap();
+        assert_eq!(total, BigUint::from(2u32).pow(size) - BigUint::from(1u32));
+        assert_eq!(count_total_cards::<u64>(&cards[136..]), Ok(u64::MAX));
+        if checked::ENABLED {
+            assert!(matches!(count_total_cards::<u64>(&cards[135..]), Err(AocError::Overflow { .. })));
+        }
+    }
+
+    #[test]
+    fn test_audit_cards() {
+        let input = example_input(4, "scratchcards");
+        let audits = audit_cards::<u64>(&parse_cards(&input).unwrap()).unwrap();
+        assert_eq!(audits[0].matched, vec![83, 86, 17, 48]);
+        assert_eq!(audits[0].points, 8);
+        assert_eq!(audits[4], CardAudit {
+            id: 5,
+            matched: vec![],
+            points: 0,
+            copies: 14,
+            contributors: vec![
+                CardContribution { card: 1, copies: 1 },
+                CardContribution { card: 3, copies: 4 },
+                CardContribution { card: 4, copies: 8 },
+            ],
+        });
+
+        let table = audit_table(&audits);
+        assert_eq!(table.lines().count(), 7);
+        assert!(table.lines().nth(5).unwrap().ends_with("| 1 (1), 3 (4), 4 (8)"));
+
+        let json: serde_json::Value = serde_json::from_str(&audit_json(&audits)).unwrap();
+        assert_eq!(json[1]["copies"], "2");
+        assert_eq!(json[1]["contributors"], serde_json::json!([{ "card": 1, "copies": "1" }]));
+    }
+
+    #[test]
+    fn test_audit_cards_big() {
+        // Far more copies than a u64 can count
+        let size: u32 = 100;
+        let cards = cascading_cards(size);
+
+        let audits = audit_cards::<BigUint>(&cards).unwrap();
+        assert_eq!(audits[0].points, BigUint::from(2u32).pow(size - 2));
+        let copies = BigUint::from(2u32).pow(size - 1);
+        assert_eq!(audits[99].copies, copies);
+        let json: serde_json::Value = serde_json::from_str(&audit_json(&audits)).unwrap();
+        assert_eq!(json[99]["copies"], copies.to_string());
+    }
+
+    #[test]
+    fn test_parse_card() {
+        let card = parse_card("Card 7:  1 21 100|1000   5 21  ").unwrap();
+        assert_eq!(card, Card {
+            id: 7,
+            winning: vec![1, 21, 100],
+            have: vec![1000, 5, 21],
+        });
+        assert_eq!(card.matches(), 1);
+
+        let cards = parse_cards("Card 1: 1 2 | 2 3\r\nCard 2: 4 | 4\r\n\r\n").unwrap();
+        assert_eq!(cards.len(), 2);
+        assert_eq!(cards[1].have, vec![4]);
     }
 
     #[test]
-    fn test_day4_puzzle02_data1() {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        let total = compute_total_cards(input);
-        assert_eq!(total, 30);
+    fn test_parse_card_errors() {
+        assert_eq!(
+            parse_card("Card 3: 1 2 2 | 5 6"),
+            Err(CardError::DuplicateNumber { card: 3, number: 2 })
+        );
+        assert_eq!(
+            parse_card("Card 3: 1 2 | 5 6 5"),
+            Err(CardError::DuplicateNumber { card: 3, number: 5 })
+        );
+        assert_eq!(
+            parse_card("Card x: 1 2 | 5 6"),
+            Err(CardError::Malformed("Card x: 1 2 | 5 6".to_string()))
+        );
+        assert!(parse_card("Card 3: 1 2 5 6").is_err());
+        assert!(parse_card("Card 3: 1 2 | ").is_err());
     }
 }
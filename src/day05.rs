@@ -1,6 +1,9 @@
 use std::{collections::HashMap, ops::Range};
 use indicatif::ProgressIterator;
 
+use crate::checked;
+use crate::error::AocError;
+
 #[derive(Clone)]
 pub enum MapType {
     Soil,
@@ -24,18 +27,26 @@ pub struct Almanac {
     pub mapping: HashMap<String, Vec<MapLine>>,
 }
 
-pub fn part1(input: &str) -> u64 {
-    let almanac = parse_input(input);
+pub fn part1(input: &str) -> Result<u64, AocError> {
+    find_closest_location(&parse_input(input))
+}
+
+pub fn part2(input: &str) -> Result<u64, AocError> {
+    // part2_orig(&input)
+    find_closest_location_reversed(&parse_input(input))
+}
+
+pub fn find_closest_location(almanac: &Almanac) -> Result<u64, AocError> {
     let mut closest_location: Option<u64> = None;
 
     for seed in almanac.seeds.iter() {
-        let soil = find_mapped_value(&almanac.mapping, &MapType::Soil, *seed);
-        let fertilizer = find_mapped_value(&almanac.mapping, &MapType::Fertilizer, soil);
-        let water = find_mapped_value(&almanac.mapping, &MapType::Water, fertilizer);
-        let light = find_mapped_value(&almanac.mapping, &MapType::Light, water);
-        let temp = find_mapped_value(&almanac.mapping, &MapType::Temp, light);
-        let humidity = find_mapped_value(&almanac.mapping, &MapType::Humidity, temp);
-        let location = find_mapped_value(&almanac.mapping, &MapType::Location, humidity);
+        let soil = find_mapped_value(&almanac.mapping, &MapType::Soil, *seed)?;
+        let fertilizer = find_mapped_value(&almanac.mapping, &MapType::Fertilizer, soil)?;
+        let water = find_mapped_value(&almanac.mapping, &MapType::Water, fertilizer)?;
+        let light = find_mapped_value(&almanac.mapping, &MapType::Light, water)?;
+        let temp = find_mapped_value(&almanac.mapping, &MapType::Temp, light)?;
+        let humidity = find_mapped_value(&almanac.mapping, &MapType::Humidity, temp)?;
+        let location = find_mapped_value(&almanac.mapping, &MapType::Location, humidity)?;
 
         if closest_location.is_none() {
             closest_location = Some(location);
@@ -44,29 +55,24 @@ pub fn part1(input: &str) -> u64 {
         }
     }
 
-    closest_location.unwrap()
+    Ok(closest_location.unwrap())
 }
 
-pub fn part2(input: &str) -> u64 {
-    // part2_orig(input)
-    part2_reversed(input)
-}
-
-pub fn part2_orig(input: &str) -> u64 {
+pub fn part2_orig(input: &str) -> Result<u64, AocError> {
     let almanac = parse_input(input);
     let mut closest_location: Option<u64> = None;
 
     for chunks in almanac.seeds.chunks(2).progress() {
         let seed_start = chunks[0] as usize;
-        let seed_range: Range<usize> = Range { start: seed_start, end: seed_start + chunks[1] as usize };
+        let seed_range: Range<usize> = Range { start: seed_start, end: checked::add(seed_start, chunks[1] as usize, "seed range")? };
         for seed_value in seed_range {
-            let soil = find_mapped_value(&almanac.mapping, &MapType::Soil, seed_value as u64);
-            let fertilizer = find_mapped_value(&almanac.mapping, &MapType::Fertilizer, soil);
-            let water = find_mapped_value(&almanac.mapping, &MapType::Water, fertilizer);
-            let light = find_mapped_value(&almanac.mapping, &MapType::Light, water);
-            let temp = find_mapped_value(&almanac.mapping, &MapType::Temp, light);
-            let humidity = find_mapped_value(&almanac.mapping, &MapType::Humidity, temp);
-            let location = find_mapped_value(&almanac.mapping, &MapType::Location, humidity);
+            let soil = find_mapped_value(&almanac.mapping, &MapType::Soil, seed_value as u64)?;
+            let fertilizer = find_mapped_value(&almanac.mapping, &MapType::Fertilizer, soil)?;
+            let water = find_mapped_value(&almanac.mapping, &MapType::Water, fertilizer)?;
+            let light = find_mapped_value(&almanac.mapping, &MapType::Light, water)?;
+            let temp = find_mapped_value(&almanac.mapping, &MapType::Temp, light)?;
+            let humidity = find_mapped_value(&almanac.mapping, &MapType::Humidity, temp)?;
+            let location = find_mapped_value(&almanac.mapping, &MapType::Location, humidity)?;
 
             if closest_location.is_none() {
                 closest_location = Some(location);
@@ -76,11 +82,11 @@ pub fn part2_orig(input: &str) -> u64 {
         }
     }
 
-    closest_location.unwrap()
+    Ok(closest_location.unwrap())
 }
 
-fn part2_reversed(input: &str) -> u64 {
-    let almanac = parse_input(input);
+// Walks the locations from the closest one up until one maps back to a seed
+pub fn find_closest_location_reversed(almanac: &Almanac) -> Result<u64, AocError> {
     let mut seed_ranges: Vec<(usize, usize)> = Vec::new();
     for chunk in almanac.seeds.chunks(2) {
         seed_ranges.push((chunk[0] as usize, chunk[1] as usize));
@@ -107,16 +113,16 @@ fn part2_reversed(input: &str) -> u64 {
             'outer: for location_range in sorted_locations.iter() {
                 let location_range_clone = location_range.clone();
                 for location in location_range_clone {
-                    let humidity = find_mapped_value_reversed(&almanac.mapping, &MapType::Location, location);
-                    let temp = find_mapped_value_reversed(&almanac.mapping, &MapType::Humidity, humidity);
-                    let light = find_mapped_value_reversed(&almanac.mapping, &MapType::Temp, temp);
-                    let water = find_mapped_value_reversed(&almanac.mapping, &MapType::Light, light);
-                    let fertilizer = find_mapped_value_reversed(&almanac.mapping, &MapType::Water, water);
-                    let soil = find_mapped_value_reversed(&almanac.mapping, &MapType::Fertilizer, fertilizer);
-                    let seed = find_mapped_value_reversed(&almanac.mapping, &MapType::Soil, soil);
+                    let humidity = find_mapped_value_reversed(&almanac.mapping, &MapType::Location, location)?;
+                    let temp = find_mapped_value_reversed(&almanac.mapping, &MapType::Humidity, humidity)?;
+                    let light = find_mapped_value_reversed(&almanac.mapping, &MapType::Temp, temp)?;
+                    let water = find_mapped_value_reversed(&almanac.mapping, &MapType::Light, light)?;
+                    let fertilizer = find_mapped_value_reversed(&almanac.mapping, &MapType::Water, water)?;
+                    let soil = find_mapped_value_reversed(&almanac.mapping, &MapType::Fertilizer, fertilizer)?;
+                    let seed = find_mapped_value_reversed(&almanac.mapping, &MapType::Soil, soil)?;
                     let thinner_seed: usize = seed.try_into().unwrap();
 
-                    if seed_exists(&seed_ranges, thinner_seed) {
+                    if seed_exists(&seed_ranges, thinner_seed)? {
                         // Found it?
                         closest_location = Some(location);
                         break 'outer;
@@ -127,21 +133,21 @@ fn part2_reversed(input: &str) -> u64 {
     }
 
     if let Some(loc) = closest_location {
-        return loc;
+        return Ok(loc);
     }
-    return 0;
+    Ok(0)
 }
 
-fn seed_exists(pairs: &Vec<(usize, usize)>, seed_value: usize) -> bool {
+fn seed_exists(pairs: &[(usize, usize)], seed_value: usize) -> Result<bool, AocError> {
     for pair in pairs.iter() {
         let start = pair.0;
-        let end = start + pair.1;
+        let end = checked::add(start, pair.1, "seed range")?;
         let range = start..end;
         if range.contains(&seed_value) {
-            return true;
+            return Ok(true);
         }
     }
-    false
+    Ok(false)
 }
 
 fn map_type_to_string(map_type: &MapType) -> String {
@@ -156,35 +162,35 @@ fn map_type_to_string(map_type: &MapType) -> String {
     }
 }
 
-fn find_mapped_value(mapping: &HashMap<String, Vec<MapLine>>, dest_type: &MapType, source_value: u64) -> u64 {
+fn find_mapped_value(mapping: &HashMap<String, Vec<MapLine>>, dest_type: &MapType, source_value: u64) -> Result<u64, AocError> {
     let dest_type_str = map_type_to_string(dest_type);
     if let Some(maps) = mapping.get(&dest_type_str) {
         for map in maps.iter() {
             if map.source.contains(&source_value) {
                 // Find the distance from the start of the source range
-                let distance = source_value - map.source.start;
-                return map.dest.start + distance;
+                let distance = checked::sub(source_value, map.source.start, "distance into source range")?;
+                return checked::add(map.dest.start, distance, "mapped value");
             }
         }
     }
-    source_value
+    Ok(source_value)
 }
 
-fn find_mapped_value_reversed(mapping: &HashMap<String, Vec<MapLine>>, dest_type: &MapType, dest_value: u64) -> u64 {
+fn find_mapped_value_reversed(mapping: &HashMap<String, Vec<MapLine>>, dest_type: &MapType, dest_value: u64) -> Result<u64, AocError> {
     let source_type_str = map_type_to_string(dest_type);
     if let Some(maps) = mapping.get(&source_type_str) {
         for map in maps.iter() {
             if map.dest.contains(&dest_value) {
                 // Find the distance from the start of the dest range
-                let distance = dest_value - map.dest.start;
-                return map.source.start + distance;
+                let distance = checked::sub(dest_value, map.dest.start, "distance into destination range")?;
+                return checked::add(map.source.start, distance, "reversed mapped value");
             }
         }
     }
-    dest_value
+    Ok(dest_value)
 }
 
-fn parse_input(input: &str) -> Almanac {
+pub fn parse_input(input: &str) -> Almanac {
     let mut seeds: Vec<u64> = Vec::new();
     let mut mapping: HashMap<String, Vec<MapLine>> = HashMap::new();
     let mut map_type: Option<MapType> = None;
@@ -263,8 +269,10 @@ fn parse_input(input: &str) -> Almanac {
 
                 if numbers.len() == 3 {
                     map_buffer.push(MapLine {
-                        dest: numbers[0]..(numbers[0] + numbers[2]),
-                        source: numbers[1]..(numbers[1] + numbers[2]),
+                        dest: numbers[0]..checked::add(numbers[0], numbers[2], "destination range")
+                            .expect("Destination range should fit in u64."),
+                        source: numbers[1]..checked::add(numbers[1], numbers[2], "source range")
+                            .expect("Source range should fit in u64."),
                     });
                 }
             }
@@ -285,113 +293,55 @@ fn parse_input(input: &str) -> Almanac {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::example_input;
+
+    crate::example_tests!(5);
 
     #[test]
     fn test_part1() {
-        let input = "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4";
+        let input = example_input(5, "almanac");
 
         // Test individual lookups
-        let almanac = parse_input(input);
-        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Soil, 79), 81);
-        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Soil, 14), 14);
-        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Soil, 55), 57);
-        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Soil, 13), 13);
-        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Soil, 53), 55);
+        let almanac = parse_input(&input);
+        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Soil, 79), Ok(81));
+        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Soil, 14), Ok(14));
+        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Soil, 55), Ok(57));
+        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Soil, 13), Ok(13));
+        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Soil, 53), Ok(55));
 
         // Test sequential lookups
-        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Fertilizer, 81), 81);
-        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Water, 81), 81);
-        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Light, 81), 74);
-        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Temp, 74), 78);
-        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Humidity, 78), 78);
-        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Location, 78), 82);
-
-        // Test final output
-        let result = part1(input);
-        assert_eq!(result, 35);
+        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Fertilizer, 81), Ok(81));
+        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Water, 81), Ok(81));
+        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Light, 81), Ok(74));
+        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Temp, 74), Ok(78));
+        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Humidity, 78), Ok(78));
+        assert_eq!(find_mapped_value(&almanac.mapping, &MapType::Location, 78), Ok(82));
     }
 
     #[test]
     fn test_part2() {
-        let input = "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4";
-        let result = part2_orig(input);
-        assert_eq!(result, 46);
+        let input = example_input(5, "almanac");
+        let result = part2_orig(&input);
+        assert_eq!(result, Ok(46));
 
         // Try the reversed method
-        let almanac = parse_input(input);
-        assert_eq!(find_mapped_value_reversed(&almanac.mapping, &MapType::Location, 82), 78);
-        assert_eq!(find_mapped_value_reversed(&almanac.mapping, &MapType::Humidity, 78), 78);
-        assert_eq!(find_mapped_value_reversed(&almanac.mapping, &MapType::Temp, 78), 74);
-        assert_eq!(find_mapped_value_reversed(&almanac.mapping, &MapType::Light, 74), 81);
-        assert_eq!(find_mapped_value_reversed(&almanac.mapping, &MapType::Water, 81), 81);
-        assert_eq!(find_mapped_value_reversed(&almanac.mapping, &MapType::Fertilizer, 81), 81);
-        assert_eq!(find_mapped_value_reversed(&almanac.mapping, &MapType::Soil, 81), 79);
-
-        let result2 = part2_reversed(input);
-        assert_eq!(result2, 46);
+        let almanac = parse_input(&input);
+        assert_eq!(find_mapped_value_reversed(&almanac.mapping, &MapType::Location, 82), Ok(78));
+        assert_eq!(find_mapped_value_reversed(&almanac.mapping, &MapType::Humidity, 78), Ok(78));
+        assert_eq!(find_mapped_value_reversed(&almanac.mapping, &MapType::Temp, 78), Ok(74));
+        assert_eq!(find_mapped_value_reversed(&almanac.mapping, &MapType::Light, 74), Ok(81));
+        assert_eq!(find_mapped_value_reversed(&almanac.mapping, &MapType::Water, 81), Ok(81));
+        assert_eq!(find_mapped_value_reversed(&almanac.mapping, &MapType::Fertilizer, 81), Ok(81));
+        assert_eq!(find_mapped_value_reversed(&almanac.mapping, &MapType::Soil, 81), Ok(79));
+    }
+
+    #[test]
+    fn test_mapped_value_overflow() {
+        if checked::ENABLED {
+            let line = MapLine { dest: u64::MAX..u64::MAX, source: 0..10 };
+            let mapping = HashMap::from([(map_type_to_string(&MapType::Soil), vec![line])]);
+            let result = find_mapped_value(&mapping, &MapType::Soil, 5);
+            assert!(matches!(result, Err(AocError::Overflow { .. })));
+        }
     }
 }
@@ -8,33 +8,36 @@ use nom::{
 };
 use nom_supreme::ParserExt;
 
-pub fn part1(input: &str) -> u64 {
+use crate::checked;
+use crate::error::AocError;
+
+pub fn part1(input: &str) -> Result<u64, AocError> {
     let lines: Vec<&str> = input.lines().collect();
     if lines.len() != 2 {
-        return 0;
+        return Ok(0);
     }
 
     if let Ok((_, numbers)) = parse_input(input) {
         let times = numbers.0;
         let distances = numbers.1;
 
-        let ways: u64 = times.iter().zip(distances).map(|(time, distance)| {
+        let ways = times.iter().zip(distances).map(|(time, distance)| {
             get_ways_to_win(*time, distance)
-        }).product();
+        }).collect::<Result<Vec<u64>, AocError>>()?;
 
-        return ways;
+        return checked::product(ways, "product of ways to win");
     }
 
-    0
+    Ok(0)
 }
 
-pub fn part2(input: &str) -> u64 {
+pub fn part2(input: &str) -> Result<u64, AocError> {
     if let Ok((_, numbers)) = parse_input2(input) {
         let time = numbers.0;
         let distance = numbers.1;
         return get_ways_to_win(time, distance);
     }
-    return 0;
+    Ok(0)
 }
 
 fn parse_input(input: &str) -> IResult<&str, (Vec<u64>, Vec<u64>)> {
@@ -78,66 +81,53 @@ fn extract_number(line: &str) -> IResult<&str, u64> {
 
 // Hold for hold milliseconds and returns the distance run in millimeters
 // max_time constraint where race only last max_time in milliseconds
-fn run_race(hold: u64, max_time: u64) -> u64 {
+fn run_race(hold: u64, max_time: u64) -> Result<u64, AocError> {
     if hold >= max_time {
-        return 0;
+        return Ok(0);
     }
 
     if hold == 0 {
-        return 0;
+        return Ok(0);
     }
 
-    (max_time - hold) * hold
+    checked::mul(max_time - hold, hold, "race distance")
 }
 
-fn get_ways_to_win(max_time: u64, to_beat: u64) -> u64{
+fn get_ways_to_win(max_time: u64, to_beat: u64) -> Result<u64, AocError> {
     let mut ways: u64 = 0;
     for hold in 1..max_time {
-        let distance = run_race(hold, max_time);
+        let distance = run_race(hold, max_time)?;
         if distance > to_beat {
             ways += 1;
         }
     }
-    ways
+    Ok(ways)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    crate::example_tests!(6);
+
     #[test]
     fn test_run_race() {
-        assert_eq!(run_race(0, 7), 0);
-        assert_eq!(run_race(1, 7), 6);
-        assert_eq!(run_race(2, 7), 10);
-        assert_eq!(run_race(3, 7), 12);
-        assert_eq!(run_race(4, 7), 12);
-        assert_eq!(run_race(5, 7), 10);
-        assert_eq!(run_race(6, 7), 6);
-        assert_eq!(run_race(7, 7), 0);
+        assert_eq!(run_race(0, 7), Ok(0));
+        assert_eq!(run_race(1, 7), Ok(6));
+        assert_eq!(run_race(2, 7), Ok(10));
+        assert_eq!(run_race(3, 7), Ok(12));
+        assert_eq!(run_race(4, 7), Ok(12));
+        assert_eq!(run_race(5, 7), Ok(10));
+        assert_eq!(run_race(6, 7), Ok(6));
+        assert_eq!(run_race(7, 7), Ok(0));
+        if checked::ENABLED {
+            assert!(matches!(run_race(1 << 32, 1 << 33), Err(AocError::Overflow { .. })));
+        }
     }
 
     #[test]
     fn test_get_ways_to_win() {
-        assert_eq!(get_ways_to_win(7, 9), 4);
-    }
-
-    #[test]
-    fn test_part1() {
-        let input = "Time:      7  15   30
-Distance:  9  40  200";
-
-        // Test final output
-        let result = part1(input);
-        assert_eq!(result, 288);
+        assert_eq!(get_ways_to_win(7, 9), Ok(4));
     }
 
-    #[test]
-    fn test_part2() {
-        let input = "Time:      7  15   30
-Distance:  9  40  200";
-
-        let result = part2(input);
-        assert_eq!(result, 71503);
-    }
 }
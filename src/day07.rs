@@ -8,6 +8,9 @@ use nom::{
     Parser,
 };
 
+use crate::checked;
+use crate::error::AocError;
+
 #[derive(Clone, Debug, PartialEq)]
 enum HandType {
     FiveOfAKind,
@@ -26,33 +29,42 @@ struct Hand<'a> {
     bid: u32,
 }
 
-pub fn part1(input: &str) -> u32 {
-    if let Ok((_, items)) = parse_input(input) {
-        let card_map: BTreeMap<&str, u32> = create_card_types();
-        let hands: Vec<Hand> = convert_to_hands(items);
-        let sorted_hands = sort_hands(hands, &card_map);
-        let winnings: u32 = sorted_hands.iter().enumerate().map(|(i, hand)| {
-            (i as u32 + 1) * hand.bid
-        }).sum();
-        return winnings;
-    }
-    0
+pub fn part1(input: &str) -> Result<u64, AocError> {
+    total_winnings(&parse_hands(input))
 }
 
-pub fn part2(input: &str) -> u32 {
-    if let Ok((_, items)) = parse_input(input) {
-        let card_map: BTreeMap<&str, u32> = create_card_types_v2();
-        let hands: Vec<Hand> = convert_to_hands_v2(items);
-        let sorted_hands = sort_hands(hands, &card_map);
-        let winnings: u32 = sorted_hands.iter().enumerate().map(|(i, hand)| {
-            (i as u32 + 1) * hand.bid
-        }).sum();
-        return winnings;
-    }
-    0
+pub fn part2(input: &str) -> Result<u64, AocError> {
+    total_winnings_with_jokers(&parse_hands(input))
+}
+
+// Cards and bid of every hand, no hands at all when the input is malformed
+pub fn parse_hands(input: &str) -> Vec<(&str, u32)> {
+    parse_input(input).map(|(_, items)| items).unwrap_or_default()
+}
+
+pub fn total_winnings(items: &[(&str, u32)]) -> Result<u64, AocError> {
+    let card_map: BTreeMap<&str, u32> = create_card_types();
+    let hands: Vec<Hand> = convert_to_hands(items);
+    let sorted_hands = sort_hands(hands, &card_map);
+    winnings(&sorted_hands)
+}
+
+pub fn total_winnings_with_jokers(items: &[(&str, u32)]) -> Result<u64, AocError> {
+    let card_map: BTreeMap<&str, u32> = create_card_types_v2();
+    let hands: Vec<Hand> = convert_to_hands_v2(items);
+    let sorted_hands = sort_hands(hands, &card_map);
+    winnings(&sorted_hands)
 }
 
-fn convert_to_hands(items: Vec<(&str, u32)>) -> Vec<Hand> {
+// Each hand wins its bid times its rank, the weakest hand having rank 1
+fn winnings(sorted_hands: &[Hand]) -> Result<u64, AocError> {
+    let amounts = sorted_hands.iter().enumerate().map(|(i, hand)| {
+        checked::mul(i as u64 + 1, hand.bid as u64, "hand winnings")
+    }).collect::<Result<Vec<u64>, AocError>>()?;
+    checked::sum(amounts, "total winnings")
+}
+
+fn convert_to_hands<'a>(items: &[(&'a str, u32)]) -> Vec<Hand<'a>> {
     let hands: Vec<Hand> = items.iter().map(|x| {
         Hand {
             cards: x.0,
@@ -64,7 +76,7 @@ fn convert_to_hands(items: Vec<(&str, u32)>) -> Vec<Hand> {
     hands
 }
 
-fn convert_to_hands_v2(items: Vec<(&str, u32)>) -> Vec<Hand> {
+fn convert_to_hands_v2<'a>(items: &[(&'a str, u32)]) -> Vec<Hand<'a>> {
     let hands: Vec<Hand> = items.iter().map(|x| {
         let v_cards = morph_cards(x.0);
         Hand {
@@ -228,6 +240,8 @@ fn morph_cards(cards: &str) -> String {
 mod tests {
     use super::*;
 
+    crate::example_tests!(7);
+
     #[test]
     fn test_hand_types() {
         assert_eq!(get_hand_type("AAAAA"), HandType::FiveOfAKind);
@@ -239,19 +253,6 @@ mod tests {
         assert_eq!(get_hand_type("23456"), HandType::HighCard);
     }
 
-    #[test]
-    fn test_part1() {
-        let input = "32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483";
-
-        // Test final output
-        let result = part1(input);
-        assert_eq!(result, 6440);
-    }
-
     #[test]
     fn test_card_morph() {
         assert_eq!(morph_cards("QJJQ2"), "QQQQ2".to_string());
@@ -261,15 +262,4 @@ QQQJA 483";
         assert_eq!(morph_cards("QQQJA"), "QQQQA".to_string());
     }
 
-    #[test]
-    fn test_part2() {
-        let input = "32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483";
-
-        let result = part2(input);
-        assert_eq!(result, 5905);
-    }
 }
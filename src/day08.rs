@@ -1,5 +1,5 @@
 use std::collections::BTreeMap;
-use num::integer::lcm;
+use num::integer::gcd;
 use nom::{
     character::complete::{alphanumeric1, char},
     sequence::{separated_pair, delimited},
@@ -10,6 +10,9 @@ use nom::{
     Parser,
 };
 
+use crate::checked;
+use crate::error::AocError;
+
 #[derive(Debug, Clone)]
 struct MapNode<'a> {
     left: &'a str,
@@ -22,36 +25,50 @@ enum Direction {
     Right,
 }
 
-pub fn part1<'a>(input: &'a str) -> u32 {
-    let (moves_str, map) = input.split_once("\n\n").expect("Invalid map.");
-    let (_, moves) = parse_moves(moves_str).expect("Invalid moves list.");
-    let map: BTreeMap<&'a str, MapNode<'a>> = parse_map(map);
+// The left/right instructions and the nodes they walk through
+#[derive(Debug, Clone)]
+pub struct Network<'a> {
+    moves: Vec<Direction>,
+    map: BTreeMap<&'a str, MapNode<'a>>,
+}
 
-    // Find number of moves
-    find_moves(moves, map, "AAA", "ZZZ")
+pub fn part1(input: &str) -> Result<u64, AocError> {
+    count_moves(&parse_network(input))
 }
 
-pub fn part2<'a>(input: &'a str) -> usize {
+pub fn part2(input: &str) -> Result<u64, AocError> {
+    count_ghost_moves(&parse_network(input))
+}
+
+pub fn parse_network<'a>(input: &'a str) -> Network<'a> {
     let (moves_str, map) = input.split_once("\n\n").expect("Invalid map.");
     let (_, moves) = parse_moves(moves_str).expect("Invalid moves list.");
     let map: BTreeMap<&'a str, MapNode<'a>> = parse_map(map);
+    Network { moves, map }
+}
 
+pub fn count_moves(network: &Network) -> Result<u64, AocError> {
     // Find number of moves
-    find_ghost_moves(moves, map, "A", "Z")
+    find_moves(&network.moves, &network.map, "AAA", "ZZZ")
+}
+
+pub fn count_ghost_moves(network: &Network) -> Result<u64, AocError> {
+    // Find number of moves
+    Ok(find_ghost_moves(&network.moves, &network.map, "A", "Z")? as u64)
 }
 
 fn find_moves<'a>(
-    moves: Vec<Direction>,
-    map: BTreeMap<&'a str, MapNode<'a>>,
+    moves: &[Direction],
+    map: &BTreeMap<&'a str, MapNode<'a>>,
     start: &str, end: &str,
-) -> u32 {
-    let mut steps: u32 = 0;
+) -> Result<u64, AocError> {
+    let mut steps: u64 = 0;
     let found: bool = false;
 
     let mut current = start;
     'upper: while !found {
         for step in moves.iter() {
-            steps += 1;
+            steps = checked::add(steps, 1, "move count")?;
             let node = map.get(current).expect("Node not found.");
             match *step {
                 Direction::Right => {
@@ -71,15 +88,15 @@ fn find_moves<'a>(
             };
         }
     }
-    steps
+    Ok(steps)
 }
 
 fn find_ghost_moves<'a>(
-    moves: Vec<Direction>,
-    map: BTreeMap<&'a str, MapNode<'a>>,
+    moves: &[Direction],
+    map: &BTreeMap<&'a str, MapNode<'a>>,
     start_end: &str,
     end_end: &str,
-) -> usize {
+) -> Result<usize, AocError> {
     let starting_nodes: Vec<&'a str> = map
         .keys()
         .filter(|k| k.ends_with(start_end))
@@ -88,17 +105,17 @@ fn find_ghost_moves<'a>(
 
     // Collect steps for each
     let steps: Vec<usize> = starting_nodes.iter().map(|start| {
-        find_ghost_moves_single(&moves, &map, start, end_end)
+        find_ghost_moves_single(moves, map, start, end_end)
     }).collect();
 
     if steps.len() >= 2 {
         return lcm_vec(&steps);
     }
-    0
+    Ok(0)
 }
 
 fn find_ghost_moves_single<'a>(
-    moves: &Vec<Direction>,
+    moves: &[Direction],
     map: &BTreeMap<&'a str, MapNode<'a>>,
     start: &str,
     pattern: &str,
@@ -160,80 +177,43 @@ fn parse_directions(line: &str) -> IResult<&str, (&str, &str)> {
     separated_pair(alphanumeric1, tag(", "), alphanumeric1).parse(line)
 }
 
-fn lcm_vec(input: &Vec<usize>) -> usize {
-    if input.len() == 2 {
-        return lcm(input[0], input[1]);
-    } else {
-        let first_num = input[0];
-        let the_rest: Vec<usize> = input.iter().skip(1).map(|x| *x).collect();
-        let second_num = lcm_vec(&the_rest);
-        return lcm(first_num, second_num);
-    }
+// Least common multiple of all the cycle lengths, dividing by the gcd before
+// multiplying so that only a result that really does not fit overflows
+fn lcm_vec(input: &[usize]) -> Result<usize, AocError> {
+    input.iter().try_fold(1, |acc, num| {
+        if acc == 0 || *num == 0 {
+            return Ok(0);
+        }
+        checked::mul(acc / gcd(acc, *num), *num, "ghost move cycle")
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part1() {
-        let input = "RL
-
-AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)";
-
-        // Test final output
-        let result = part1(input);
-        assert_eq!(result, 2);
-    }
-
-    #[test]
-    fn test_part1_data2() {
-        let input = "LLR
-
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)";
-
-        // Test final output
-        let result = part1(input);
-        assert_eq!(result, 6);
-    }
+    crate::example_tests!(8);
 
     #[test]
     fn test_lcm_2_items() {
         let input: Vec<usize> = vec![2, 3];
         let result = lcm_vec(&input);
-        assert_eq!(result, 6);
+        assert_eq!(result, Ok(6));
     }
 
     #[test]
     fn test_lcm_4_items() {
         let input: Vec<usize> = vec![5, 10, 15, 25];
         let result = lcm_vec(&input);
-        assert_eq!(result, 150);
+        assert_eq!(result, Ok(150));
     }
 
     #[test]
-    fn test_part2() {
-        let input = "LR
-
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)";
-
-        // Test final output
-        let result = part2(input);
-        assert_eq!(result, 6);
+    fn test_lcm_overflow() {
+        if checked::ENABLED {
+            let input: Vec<usize> = vec![usize::MAX, usize::MAX - 1];
+            assert!(matches!(lcm_vec(&input), Err(AocError::Overflow { .. })));
+        }
     }
+
 }
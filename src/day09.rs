@@ -5,40 +5,56 @@ use nom::{
     Parser,
 };
 
-pub fn part1(input: &str) -> i32 {
+use crate::checked;
+use crate::error::AocError;
+
+pub fn part1(input: &str) -> Result<i64, AocError> {
+    sum_next_values(&parse_histories(input))
+}
+
+pub fn part2(input: &str) -> Result<i64, AocError> {
+    sum_prev_values(&parse_histories(input))
+}
+
+pub fn parse_histories(input: &str) -> Vec<Vec<i64>> {
     let (_, histories) = parse_input(input).expect("Invalid histories input.");
-    histories.iter().map(|history| {
+    histories
+}
+
+pub fn sum_next_values(histories: &[Vec<i64>]) -> Result<i64, AocError> {
+    let values = histories.iter().map(|history| {
         find_next_value(history)
-    }).sum()
+    }).collect::<Result<Vec<i64>, AocError>>()?;
+    checked::sum(values, "sum of next values")
 }
 
-pub fn part2(input: &str) -> i32 {
-    let (_, histories) = parse_input(input).expect("Invalid histories input.");
-    histories.iter().map(|history| {
+pub fn sum_prev_values(histories: &[Vec<i64>]) -> Result<i64, AocError> {
+    let values = histories.iter().map(|history| {
         find_prev_value(history)
-    }).sum()
+    }).collect::<Result<Vec<i64>, AocError>>()?;
+    checked::sum(values, "sum of previous values")
 }
 
-fn find_next_value(history: &Vec<i32>) -> i32 {
+fn find_next_value(history: &[i64]) -> Result<i64, AocError> {
     // Find the bottom zeroes
-    let mut current: Vec<i32> = history.clone();
-    let mut sequences: Vec<Vec<i32>> = Vec::new();
-    sequences.push(history.clone());
+    let mut current: Vec<i64> = history.to_vec();
+    let mut sequences: Vec<Vec<i64>> = Vec::new();
+    sequences.push(history.to_vec());
 
     let mut is_bottom: bool = false;
 
     while !is_bottom {
-        let mut next: Vec<i32> = Vec::new();
+        let mut next: Vec<i64> = Vec::new();
         for i in 1..current.len() {
-            let a: i32 = current[i - 1];
-            let b: i32 = current[i];
-            let diff: i32 = b - a;
+            let a: i64 = current[i - 1];
+            let b: i64 = current[i];
+            let diff: i64 = checked::sub(b, a, "history difference")?;
             next.push(diff);
         }
 
         sequences.push(next.clone());
         
-        let sum: i32 = next.iter().sum();
+        let sum: i64 = checked::sum(next.iter().copied(), "difference sum")?;
         if sum == 0 {
             is_bottom = true;
         }
@@ -47,38 +63,38 @@ fn find_next_value(history: &Vec<i32>) -> i32 {
     }
 
     // Add 1 entry from the bottom then compute to the top
-    let mut right: i32 = 0;
+    let mut right: i64 = 0;
     for i in 1..sequences.len() {
         let index: usize = sequences.len() - (i + 1);
         let series = &sequences[index];
-        let left: i32 = *series.last().expect("Series not be empty.");
-        let new_right: i32 = right + left;
+        let left: i64 = *series.last().expect("Series not be empty.");
+        let new_right: i64 = checked::add(right, left, "next value")?;
         right = new_right;
     }
 
-    right
+    Ok(right)
 }
 
-fn find_prev_value(history: &Vec<i32>) -> i32 {
+fn find_prev_value(history: &[i64]) -> Result<i64, AocError> {
     // Find the bottom zeroes
-    let mut current: Vec<i32> = history.clone();
-    let mut sequences: Vec<Vec<i32>> = Vec::new();
-    sequences.push(history.clone());
+    let mut current: Vec<i64> = history.to_vec();
+    let mut sequences: Vec<Vec<i64>> = Vec::new();
+    sequences.push(history.to_vec());
 
     let mut is_bottom: bool = false;
 
     while !is_bottom {
-        let mut next: Vec<i32> = Vec::new();
+        let mut next: Vec<i64> = Vec::new();
         for i in 1..current.len() {
-            let a: i32 = current[i - 1];
-            let b: i32 = current[i];
-            let diff: i32 = b - a;
+            let a: i64 = current[i - 1];
+            let b: i64 = current[i];
+            let diff: i64 = checked::sub(b, a, "history difference")?;
             next.push(diff);
         }
 
         sequences.push(next.clone());
         
-        let sum: i32 = next.iter().sum();
+        let sum: i64 = checked::sum(next.iter().copied(), "difference sum")?;
         if sum == 0 {
             is_bottom = true;
         }
@@ -87,49 +103,37 @@ fn find_prev_value(history: &Vec<i32>) -> i32 {
     }
 
     // Add 1 entry from the bottom then compute to the top
-    let mut left: i32 = 0;
+    let mut left: i64 = 0;
     for i in 1..sequences.len() {
         let index: usize = sequences.len() - (i + 1);
         let series = &sequences[index];
-        let right: i32 = *series.first().expect("Series not be empty.");
-        let new_left: i32 = right - left;
+        let right: i64 = *series.first().expect("Series not be empty.");
+        let new_left: i64 = checked::sub(right, left, "previous value")?;
         left = new_left;
     }
 
-    left
+    Ok(left)
 }
 
-fn parse_input(input: &str) -> IResult<&str, Vec<Vec<i32>>> {
+fn parse_input(input: &str) -> IResult<&str, Vec<Vec<i64>>> {
     separated_list1(line_ending, parse_line).parse(input)
 }
 
-fn parse_line(line: &str) -> IResult<&str, Vec<i32>> {
-    separated_list1(space1, complete::i32).parse(line)
+fn parse_line(line: &str) -> IResult<&str, Vec<i64>> {
+    separated_list1(space1, complete::i64).parse(line)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part1() {
-        let input = "0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45";
-
-        // Test final output
-        let result = part1(input);
-        assert_eq!(result, 114);
-    }
+    crate::example_tests!(9);
 
     #[test]
-    fn test_part2() {
-        let input = "0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45";
-
-        // Test final output
-        let result = part2(input);
-        assert_eq!(result, 2);
+    fn test_overflow() {
+        if checked::ENABLED {
+            let histories = parse_histories("-9223372036854775807 9223372036854775807");
+            assert!(matches!(sum_next_values(&histories), Err(AocError::Overflow { .. })));
+        }
     }
 }
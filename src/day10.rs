@@ -1,8 +1,10 @@
-use glam::IVec2;
+use std::fmt;
 use std::ops::Range;
 
+use crate::grid::Grid;
+
 #[derive(Debug, Clone, PartialEq)]
-enum Tile {
+pub enum Tile {
     Vertical,
     Horizontal,
     ElbowNE,
@@ -20,42 +22,74 @@ enum MarkedTile {
     Regular,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-struct Coord {
-    x: usize,
-    y: usize,
-}
-
 #[derive(Debug)]
 struct MoveMap {
     source: Vec<Tile>,
     dest: Vec<Tile>,
-    movement: IVec2,
+    movement: (isize, isize),
 }
 
-pub fn part1(input: &str) -> u32 {
-    let grid: Vec<Vec<Tile>> = parse_input(input);
+impl Tile {
+    fn from_char(ch: char) -> Option<Tile> {
+        match ch {
+            '|' => Some(Tile::Vertical),
+            '-' => Some(Tile::Horizontal),
+            'L' => Some(Tile::ElbowNE),
+            'J' => Some(Tile::ElbowNW),
+            '7' => Some(Tile::ElbowSW),
+            'F' => Some(Tile::ElbowSE),
+            '.' => Some(Tile::Ground),
+            'S' => Some(Tile::Start),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ch = match self {
+            Tile::Vertical => '|',
+            Tile::Horizontal => '-',
+            Tile::ElbowNE => 'L',
+            Tile::ElbowNW => 'J',
+            Tile::ElbowSW => '7',
+            Tile::ElbowSE => 'F',
+            Tile::Ground => '.',
+            Tile::Start => 'S',
+        };
+        write!(f, "{}", ch)
+    }
+}
+
+pub fn part1(input: &str) -> usize {
+    find_farthest_steps(&parse_input(input))
+}
+
+pub fn part2(input: &str) -> usize {
+    count_enclosed_tiles(&parse_input(input))
+}
+
+pub fn find_farthest_steps(grid: &Grid<Tile>) -> usize {
     let directions = create_move_directions();
-    let start = find_start(&grid);
-    let initial_moves = get_initial_moves(&grid, &directions, &start);
-    if let Some(steps) = find_loop(&grid, &directions, &start, &initial_moves.0) {
-        return (steps.len() / 2) as u32;
+    let start = find_start(grid);
+    let initial_moves = get_initial_moves(grid, &directions, &start);
+    if let Some(steps) = find_loop(grid, &directions, &start, &initial_moves.0) {
+        return steps.len() / 2;
     }
     0
 }
 
-pub fn part2(input: &str) -> u32 {
-    let grid: Vec<Vec<Tile>> = parse_input(input);
+pub fn count_enclosed_tiles(grid: &Grid<Tile>) -> usize {
     let directions = create_move_directions();
-    let start = find_start(&grid);
-    let initial_moves = get_initial_moves(&grid, &directions, &start);
-    if let Some(steps) = find_loop(&grid, &directions, &start, &initial_moves.0) {
-        return find_enclosure(&grid, &steps) as u32;
+    let start = find_start(grid);
+    let initial_moves = get_initial_moves(grid, &directions, &start);
+    if let Some(steps) = find_loop(grid, &directions, &start, &initial_moves.0) {
+        return find_enclosure(grid, &steps);
     }
     0
 }
 
-fn find_enclosure(grid: &Vec<Vec<Tile>>, steps: &Vec<IVec2>) -> usize {
+fn find_enclosure(grid: &Grid<Tile>, steps: &[(usize, usize)]) -> usize {
     // We can mark boundaries by only looking for pipe that is part of our loop
     // Mark all pipes across the grid
     // Scan row by row and find a group of tiles that are not part of the loop
@@ -65,11 +99,10 @@ fn find_enclosure(grid: &Vec<Vec<Tile>>, steps: &Vec<IVec2>) -> usize {
     // If each are odd, tiles are inside the loop
     
     // Create a marked grid
-    let empty_row: Vec<MarkedTile> = vec![MarkedTile::Regular; grid[0].len()];
-    let mut marked_grid: Vec<Vec<MarkedTile>> = vec![empty_row; grid.len()];
+    let mut marked_grid: Grid<MarkedTile> = Grid::filled(grid.rows(), grid.cols(), MarkedTile::Regular);
 
     for step in steps.iter() {
-        let tile = &grid[step.x as usize][step.y as usize];
+        let tile = &grid[*step];
         let marker = match tile {
             Tile::Start => MarkedTile::Enclosure,
             Tile::Vertical => MarkedTile::Enclosure,
@@ -80,12 +113,12 @@ fn find_enclosure(grid: &Vec<Vec<Tile>>, steps: &Vec<IVec2>) -> usize {
             Tile::ElbowSW => MarkedTile::Enclosure,
             _ => MarkedTile::Regular,
         };
-        marked_grid[step.x as usize][step.y as usize] = marker; 
+        marked_grid[*step] = marker;
     }
 
     let mut enclosed_count: usize = 0;
 
-    for (x, row) in marked_grid.iter().enumerate() {
+    for (x, row) in marked_grid.iter_rows().enumerate() {
         let mut contents: Vec<Range<usize>> = Vec::new();
         let mut buffer_start: Option<usize> = None;
         let mut buffer_end: Option<usize> = None;
@@ -94,31 +127,27 @@ fn find_enclosure(grid: &Vec<Vec<Tile>>, steps: &Vec<IVec2>) -> usize {
             if marker == &MarkedTile::Enclosure {
                 // Contents always start with an enclosure pipe
                 // Flush buffer if there are any
-                if buffer_start.is_some() && buffer_end.is_some() {
-                    if buffer_start.unwrap() <= buffer_end.unwrap() {
-                        contents.push(Range { start: buffer_start.unwrap(), end: buffer_end.unwrap() });
+                if let (Some(start), Some(end)) = (buffer_start, buffer_end) {
+                    if start <= end {
+                        contents.push(Range { start, end });
                     }
                 }
 
                 // Buffer start on the next tile
                 buffer_start = Some(y + 1);
                 buffer_end = Some(y + 1);
-            } else if marker == &MarkedTile::Regular {
-                if buffer_start.is_some() {
-                    buffer_end = Some(y);
-                }
+            } else if marker == &MarkedTile::Regular && buffer_start.is_some() {
+                buffer_end = Some(y);
             }
         }
 
         for content_range in contents.iter() {
-            let (left, _right) = count_walls(&marked_grid, x, &content_range);
-            if left > 0 {
-                if left % 2 != 0 {
-                    for i in content_range.start..=content_range.end {
-                        let marker = &marked_grid[x][i];
-                        if marker == &MarkedTile::Regular {
-                            enclosed_count += 1;
-                        }
+            let (left, _right) = count_walls(&marked_grid, x, content_range);
+            if left > 0 && left % 2 != 0 {
+                for i in content_range.start..=content_range.end {
+                    let marker = &marked_grid[(x, i)];
+                    if marker == &MarkedTile::Regular {
+                        enclosed_count += 1;
                     }
                 }
             }
@@ -128,22 +157,22 @@ fn find_enclosure(grid: &Vec<Vec<Tile>>, steps: &Vec<IVec2>) -> usize {
     enclosed_count
 }
 
-fn count_walls(grid: &Vec<Vec<MarkedTile>>, row_index: usize, content_range: &Range<usize>) -> (u32, u32) {
+fn count_walls(grid: &Grid<MarkedTile>, row_index: usize, content_range: &Range<usize>) -> (u32, u32) {
     let mut left: u32 = 0;
     let mut right: u32 = 0;
 
     if content_range.start > 0 {
         for i in 0..content_range.start {
-            let marker = &grid[row_index][i];
+            let marker = &grid[(row_index, i)];
             if marker == &MarkedTile::Enclosure {
                 left += 1;
             }
         }
     }
 
-    if content_range.end < grid[0].len() {
-        for i in content_range.end..grid[0].len() {
-            let marker = &grid[row_index][i];
+    if content_range.end < grid.cols() {
+        for i in content_range.end..grid.cols() {
+            let marker = &grid[(row_index, i)];
             if marker == &MarkedTile::Enclosure {
                 right += 1;
             }
@@ -153,44 +182,38 @@ fn count_walls(grid: &Vec<Vec<MarkedTile>>, row_index: usize, content_range: &Ra
     (left, right)
 }
 
-fn find_loop(grid: &Vec<Vec<Tile>>, directions: &Vec<MoveMap>, start: &IVec2, current: &IVec2) -> Option<Vec<IVec2>> {
-    let mut steps: Vec<IVec2> = Vec::new();
-    steps.push(current.clone());
+fn find_loop(
+    grid: &Grid<Tile>,
+    directions: &[MoveMap],
+    start: &(usize, usize),
+    current: &(usize, usize),
+) -> Option<Vec<(usize, usize)>> {
+    let mut steps: Vec<(usize, usize)> = vec![*current];
 
-    let mut current_coord = current.clone();
-    let mut next: Option<IVec2> = next_move(grid, directions, &current_coord, start);
+    let mut current_coord = *current;
+    let mut next: Option<(usize, usize)> = next_move(grid, directions, &current_coord, start);
 
-    while next.is_some() {
-        let next_coord = next.unwrap();
-        steps.push(next_coord.clone());
+    while let Some(next_coord) = next {
+        steps.push(next_coord);
         next = next_move(grid, directions, &next_coord, &current_coord);
-        current_coord = next_coord.clone();
+        current_coord = next_coord;
     }
 
-    if steps.len() > 0 {
-        if let Some(last) = steps.last() {
-            let tile = &grid[last.x as usize][last.y as usize];
-            if *tile == Tile::Start {
-                return Some(steps);
-            }
+    if let Some(last) = steps.last() {
+        if grid[*last] == Tile::Start {
+            return Some(steps);
         }
     }
     None
 }
 
-fn get_initial_moves(grid: &Vec<Vec<Tile>>, directions: &Vec<MoveMap>, start: &IVec2) -> (IVec2, IVec2) {
-    let rows: i32 = grid.len() as i32;
-    let cols: i32 = grid[0].len() as i32;
-    let mut moves: Vec<IVec2> = Vec::new();
+fn get_initial_moves(grid: &Grid<Tile>, directions: &[MoveMap], start: &(usize, usize)) -> ((usize, usize), (usize, usize)) {
+    let mut moves: Vec<(usize, usize)> = Vec::new();
 
     for direction in directions.iter() {
-        let next = *start + direction.movement;
-
-        // Check for out of bounds
-        if next.x >= 0 && next.y >= 0 && next.x < rows && next.y < cols {
-            // Make sure that the next tile is compatible
-            let next_tile = &grid[next.x as usize][next.y as usize];
-            if direction.dest.contains(next_tile) {
+        // Make sure that the next tile is inside the grid and compatible
+        if let Some(next) = grid.step(*start, direction.movement) {
+            if direction.dest.contains(&grid[next]) {
                 moves.push(next);
             }
         }
@@ -202,25 +225,26 @@ fn get_initial_moves(grid: &Vec<Vec<Tile>>, directions: &Vec<MoveMap>, start: &I
     (moves[0], moves[1])
 }
 
-fn next_move(grid: &Vec<Vec<Tile>>, directions: &Vec<MoveMap>, start: &IVec2, prev: &IVec2) -> Option<IVec2> {
-    let rows: i32 = grid.len() as i32;
-    let cols: i32 = grid[0].len() as i32;
-    let tile = &grid[start.x as usize][start.y as usize];
-    let mut next_tile_step: Option<IVec2> = None;
+fn next_move(
+    grid: &Grid<Tile>,
+    directions: &[MoveMap],
+    start: &(usize, usize),
+    prev: &(usize, usize),
+) -> Option<(usize, usize)> {
+    let tile = &grid[*start];
+    let mut next_tile_step: Option<(usize, usize)> = None;
 
     for direction in directions.iter() {
-        let next = *start + direction.movement;
-
-        // Check for out of bounds
-        if next.x >= 0 && next.y >= 0 && next.x < rows && next.y < cols {
-            // Make sure that the next tile is compatible
-            let next_tile = &grid[next.x as usize][next.y as usize];
-            if direction.source.contains(&tile) && (direction.dest.contains(next_tile) || *next_tile == Tile::Start) {
-                // Exclude previous coords
-                if prev != &next {
-                    next_tile_step = Some(next);
-                    break;
-                }
+        // Make sure that the next tile is inside the grid and compatible
+        let Some(next) = grid.step(*start, direction.movement) else {
+            continue;
+        };
+        let next_tile = &grid[next];
+        if direction.source.contains(tile) && (direction.dest.contains(next_tile) || *next_tile == Tile::Start) {
+            // Exclude previous coords
+            if prev != &next {
+                next_tile_step = Some(next);
+                break;
             }
         }
     }
@@ -234,142 +258,48 @@ fn create_move_directions() -> Vec<MoveMap> {
         MoveMap {
             source: vec![Tile::Vertical, Tile::ElbowSE, Tile::ElbowSW],
             dest: vec![Tile::Vertical, Tile::ElbowNE, Tile::ElbowNW],
-            movement: IVec2::from((1, 0)),
+            movement: (1, 0),
         },
         // Right
         MoveMap {
             source: vec![Tile::Horizontal, Tile::ElbowNE, Tile::ElbowSE],
             dest: vec![Tile::Horizontal, Tile::ElbowSW, Tile::ElbowNW],
-            movement: IVec2::from((0, 1)),
+            movement: (0, 1),
         },
         // Up
         MoveMap {
             source: vec![Tile::Vertical, Tile::ElbowNE, Tile::ElbowNW],
             dest: vec![Tile::Vertical, Tile::ElbowSE, Tile::ElbowSW],
-            movement: IVec2::from((-1, 0)),
+            movement: (-1, 0),
         },
         // Left
         MoveMap {
             source: vec![Tile::Horizontal, Tile::ElbowSW, Tile::ElbowNW],
             dest: vec![Tile::Horizontal, Tile::ElbowNE, Tile::ElbowSE],
-            movement: IVec2::from((0, -1)),
+            movement: (0, -1),
         },
     ])
 }
 
-fn find_start(grid: &Vec<Vec<Tile>>) -> IVec2 {
-    // Find the starting position of the grid
-    for x in 0..grid.len() {
-        let row = &grid[x];
-        for y in 0..row.len() {
-            let tile = &grid[x][y];
-            if *tile == Tile::Start {
-                return IVec2::from((x as i32, y as i32));
-            }
-        }
-    }
-    panic!("Unable to find the staring position.");
-}
-
-fn parse_input(input: &str) -> Vec<Vec<Tile>> {
-    input.lines().map(|line| {
-        parse_line(line)
-    }).collect::<Vec<Vec<Tile>>>()
+fn find_start(grid: &Grid<Tile>) -> (usize, usize) {
+    grid.position(|tile| *tile == Tile::Start).expect("Grid should have a starting position.")
 }
 
-fn parse_line(line: &str) -> Vec<Tile> {
-    line.chars().map(|ch| {
-        match ch {
-            '|' => Tile::Vertical,
-            '-' => Tile::Horizontal,
-            'L' => Tile::ElbowNE,
-            'J' => Tile::ElbowNW,
-            '7' => Tile::ElbowSW,
-            'F' => Tile::ElbowSE,
-            '.' => Tile::Ground,
-            'S' => Tile::Start,
-            _ => {
-                panic!("Unexpected tile.");
-            }
-        }
-    }).collect::<Vec<Tile>>()
+pub fn parse_input(input: &str) -> Grid<Tile> {
+    Grid::parse(input, Tile::from_char).expect("Tiles should be valid.")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part1_data1() {
-        let input = ".....
-.S-7.
-.|.|.
-.L-J.
-.....";
-
-        let result = part1(input);
-        assert_eq!(result, 4);
-    }
-
-    #[test]
-    fn test_part1_data2() {
-        let input = "..F7.
-.FJ|.
-SJ.L7
-|F--J
-LJ...";
-
-        let result = part1(input);
-        assert_eq!(result, 8);
-    }
-
-    #[test]
-    fn test_part2_data1() {
-        let input = "...........
-.S-------7.
-.|F-----7|.
-.||.....||.
-.||.....||.
-.|L-7.F-J|.
-.|..|.|..|.
-.L--J.L--J.
-...........";
-
-        let result = part2(input);
-        assert_eq!(result, 4);
-    }
-
-    #[test]
-    fn test_part2_data2() {
-        let input = ".F----7F7F7F7F-7....
-.|F--7||||||||FJ....
-.||.FJ||||||||L7....
-FJL7L7LJLJ||LJ.L-7..
-L--J.L7...LJS7F-7L7.
-....F-J..F7FJ|L7L7L7
-....L7.F7||L7|.L7L7|
-.....|FJLJ|FJ|F7|.LJ
-....FJL-7.||.||||...
-....L---J.LJ.LJLJ...";
-
-        let result = part2(input);
-        assert_eq!(result, 8);
-    }
+    crate::example_tests!(10);
 
     #[test]
-    fn test_part2_data3() {
-        let input = "FF7FSF7F7F7F7F7F---7
-L|LJ||||||||||||F--J
-FL-7LJLJ||||||LJL-77
-F--JF--7||LJLJ7F7FJ-
-L---JF-JLJ.||-FJLJJ7
-|F|F-JF---7F7-L7L|7|
-|FFJF7L7F-JF7|JL---7
-7-L-JL7||F7|L7F-7F7|
-L.L7LFJ|||||FJL7||LJ
-L7JLJL-JLJLJL--JLJ.L";
-
-        let result = part2(input);
-        assert_eq!(result, 10);
+    fn test_parse_input() {
+        let input = "..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...";
+        let grid = parse_input(input);
+        assert_eq!(find_start(&grid), (2, 0));
+        assert_eq!(grid.to_string(), input);
     }
 }
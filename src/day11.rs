@@ -1,6 +1,10 @@
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 use glam::I64Vec2;
-use num::abs;
+
+use crate::checked;
+use crate::error::AocError;
+use crate::grid::Grid;
 
 #[derive(Debug, Clone)]
 struct Galaxy {
@@ -9,38 +13,50 @@ struct Galaxy {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-enum SkyItem {
+pub enum SkyItem {
     Galaxy,
     Space,
 }
 
-pub fn part1(input: &str) -> u64 {
+impl fmt::Display for SkyItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SkyItem::Galaxy => write!(f, "#"),
+            SkyItem::Space => write!(f, "."),
+        }
+    }
+}
+
+pub fn part1(input: &str) -> Result<u64, AocError> {
     let sky = parse_input(input);
     sum_shortest_paths(&sky, 2)
 }
 
-pub fn part2(input: &str) -> u64 {
+pub fn part2(input: &str) -> Result<u64, AocError> {
     let sky = parse_input(input);
     sum_shortest_paths(&sky, 1000000)
 }
 
-fn sum_shortest_paths(sky: &Vec<Vec<SkyItem>>, pad: i64) -> u64 {
-    let galaxies = expand_universe(&sky, pad);
+pub fn sum_shortest_paths(sky: &Grid<SkyItem>, pad: i64) -> Result<u64, AocError> {
+    let galaxies = expand_universe(sky, pad)?;
     let pairs = create_galaxy_pairs(&galaxies);
 
     let mut shortest_paths: u64 = 0;
     for pair in pairs.iter() {
-        let distance = find_manhattan_distance(&pair.0.coord, &pair.1.coord);
-        shortest_paths += distance as u64;
+        let distance = find_manhattan_distance(&pair.0.coord, &pair.1.coord)?;
+        shortest_paths = checked::add(shortest_paths, distance as u64, "sum of shortest paths")?;
     }
-    shortest_paths
+    Ok(shortest_paths)
 }
 
-fn find_manhattan_distance(source: &I64Vec2, dest: &I64Vec2) -> i64 {
-    abs(source.x - dest.x) + abs(source.y - dest.y)
+fn find_manhattan_distance(source: &I64Vec2, dest: &I64Vec2) -> Result<i64, AocError> {
+    // Larger minus smaller, so the difference needs no abs that could overflow
+    let dx = checked::sub(source.x.max(dest.x), source.x.min(dest.x), "galaxy distance")?;
+    let dy = checked::sub(source.y.max(dest.y), source.y.min(dest.y), "galaxy distance")?;
+    checked::add(dx, dy, "galaxy distance")
 }
 
-fn create_galaxy_pairs(galaxies: &Vec<Galaxy>) -> Vec<(Galaxy, Galaxy)> {
+fn create_galaxy_pairs(galaxies: &[Galaxy]) -> Vec<(Galaxy, Galaxy)> {
     let mut pairs: HashMap<I64Vec2, (Galaxy, Galaxy)> = HashMap::new();
 
     for x in 0..galaxies.len() {
@@ -50,7 +66,7 @@ fn create_galaxy_pairs(galaxies: &Vec<Galaxy>) -> Vec<(Galaxy, Galaxy)> {
             if x_item.id != y_item.id {
                 let key = I64Vec2::from((x_item.id as i64, y_item.id as i64));
                 let key_rev = I64Vec2::from((y_item.id as i64, x_item.id as i64));
-                if pairs.get(&key).is_none() && pairs.get(&key_rev).is_none() {
+                if !pairs.contains_key(&key) && !pairs.contains_key(&key_rev) {
                     pairs.insert(key, (x_item.clone(), y_item.clone()));
                 }
             }
@@ -59,147 +75,89 @@ fn create_galaxy_pairs(galaxies: &Vec<Galaxy>) -> Vec<(Galaxy, Galaxy)> {
     pairs.into_values().collect()
 }
 
-fn expand_universe(sky: &Vec<Vec<SkyItem>>, pad: i64) -> Vec<Galaxy> {
+fn expand_universe(sky: &Grid<SkyItem>, pad: i64) -> Result<Vec<Galaxy>, AocError> {
     let galaxies = collect_galaxies(sky);
 
     // Find empty rows and empty columns
-    let mut empty_rows: HashSet<usize> = HashSet::new();
-    let mut empty_cols: HashSet<usize> = HashSet::new();
-
-    // Find empty rows
-    for (i, row) in sky.iter().enumerate() {
-        let count = row.iter().filter(|x| *x == &SkyItem::Space).count();
-        if count == row.len() {
-            empty_rows.insert(i);
-        }
-    }
-
-    // Find empty cols
-    let row_len = sky[0].len();
-    for y in 0..row_len {
-        let mut count: usize = 0;
-        for x in 0..sky.len() {
-            let item = &sky[x][y];
-            if *item == SkyItem::Space {
-                count += 1;
-            }
-        }
-        if count == sky.len() {
-            empty_cols.insert(y);
-        }
-    }
+    let empty_rows: HashSet<usize> = (0..sky.rows())
+        .filter(|x| sky.row(*x).iter().all(|item| *item == SkyItem::Space))
+        .collect();
+    let empty_cols: HashSet<usize> = (0..sky.cols())
+        .filter(|y| sky.column(*y).all(|item| *item == SkyItem::Space))
+        .collect();
 
     // Simply move the galaxies away based on the padding
+    let growth = checked::sub(pad, 1, "expansion padding")?;
     galaxies.iter().map(|galaxy| {
-        let mut x = galaxy.coord.x;
-        let mut y = galaxy.coord.y;
+        let rows_before = empty_rows.iter().filter(|empty_x| (**empty_x as i64) < galaxy.coord.x).count() as i64;
+        let cols_before = empty_cols.iter().filter(|empty_y| (**empty_y as i64) < galaxy.coord.y).count() as i64;
 
-        for empty_x in empty_rows.iter() {
-            if (*empty_x as i64) < galaxy.coord.x {
-                x += pad - 1;
-            }
-        }
-
-        for empty_y in empty_cols.iter() {
-            if (*empty_y as i64) < galaxy.coord.y {
-                y += pad - 1;
-            }
-        }
-        Galaxy {
+        let x = checked::add(galaxy.coord.x, checked::mul(growth, rows_before, "row expansion")?, "galaxy row")?;
+        let y = checked::add(galaxy.coord.y, checked::mul(growth, cols_before, "column expansion")?, "galaxy column")?;
+        Ok(Galaxy {
             id: galaxy.id,
             coord: I64Vec2::from((x, y))
-        }
-    }).collect::<Vec<Galaxy>>()
+        })
+    }).collect::<Result<Vec<Galaxy>, AocError>>()
 }
 
-fn collect_galaxies(sky: &Vec<Vec<SkyItem>>) -> Vec<Galaxy> {
-    let mut galaxy_count: u64 = 0;
-    let mut galaxies: Vec<Galaxy> = Vec::new();
-    for (x, row) in sky.iter().enumerate() {
-        for (y, item) in row.iter().enumerate() {
-            if item == &SkyItem::Galaxy {
-                galaxy_count += 1;
-
-                let coord = I64Vec2::from((x as i64, y as i64));
-                galaxies.push(Galaxy { id: galaxy_count, coord });
-            }
-        }
-    }
-    galaxies
+fn collect_galaxies(sky: &Grid<SkyItem>) -> Vec<Galaxy> {
+    sky.iter()
+        .filter(|(_, item)| **item == SkyItem::Galaxy)
+        .enumerate()
+        .map(|(i, ((x, y), _))| Galaxy {
+            id: i as u64 + 1,
+            coord: I64Vec2::from((x as i64, y as i64)),
+        })
+        .collect()
 }
 
-fn parse_input(input: &str) -> Vec<Vec<SkyItem>> {
-    input.lines().map(|line| {
-        line.chars().map(|ch| {
-            match ch {
-                '#' => {
-                    SkyItem::Galaxy
-                },
-                '.' => {
-                    SkyItem::Space
-                },
-                _ => {
-                    panic!("Unexpected character.");
-                }
-            }
-        }).collect::<Vec<SkyItem>>()
-    }).collect::<Vec<Vec<SkyItem>>>()
+pub fn parse_input(input: &str) -> Grid<SkyItem> {
+    Grid::parse(input, |ch| match ch {
+        '#' => Some(SkyItem::Galaxy),
+        '.' => Some(SkyItem::Space),
+        _ => None,
+    }).expect("Sky should only hold galaxies and space.")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::example_input;
+
+    crate::example_tests!(11);
 
     #[test]
-    fn test_part1() {
-        let input = "...#......
-.......#..
-#.........
-..........
-......#...
-.#........
-.........#
-..........
-.......#..
-#...#.....";
-
-        let result = part1(input);
-        assert_eq!(result, 374);
+    fn test_parse_input() {
+        let input = example_input(11, "galaxies");
+        let sky = parse_input(&input);
+        assert_eq!(sky.to_string(), input.trim_end());
+        assert_eq!(collect_galaxies(&sky).len(), 9);
     }
 
     #[test]
     fn test_part2_data1() {
-        let input = "...#......
-.......#..
-#.........
-..........
-......#...
-.#........
-.........#
-..........
-.......#..
-#...#.....";
-
-        let sky = parse_input(input);
+        let input = example_input(11, "galaxies");
+
+        let sky = parse_input(&input);
         let result = sum_shortest_paths(&sky, 10);
-        assert_eq!(result, 1030);
+        assert_eq!(result, Ok(1030));
     }
 
     #[test]
     fn test_part2_data2() {
-        let input = "...#......
-.......#..
-#.........
-..........
-......#...
-.#........
-.........#
-..........
-.......#..
-#...#.....";
-
-        let sky = parse_input(input);
+        let input = example_input(11, "galaxies");
+
+        let sky = parse_input(&input);
         let result = sum_shortest_paths(&sky, 100);
-        assert_eq!(result, 8410);
+        assert_eq!(result, Ok(8410));
+    }
+
+    #[test]
+    fn test_expansion_overflow() {
+        if checked::ENABLED {
+            let sky = parse_input(&example_input(11, "galaxies"));
+            assert!(matches!(sum_shortest_paths(&sky, i64::MAX / 2), Err(AocError::Overflow { .. })));
+        }
     }
 }
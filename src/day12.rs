@@ -3,239 +3,285 @@ use nom::{
     bytes::complete::is_a,
     IResult,
 };
-use std::cell::RefCell;
-use std::collections::HashMap;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
-use std::rc::Rc;
-use indicatif::ProgressIterator;
-use itertools::Itertools;
+use std::fmt::Display;
+use num::{BigUint, CheckedAdd, One, Zero};
+use rand::distributions::uniform::SampleUniform;
+use rand::Rng;
+
+use crate::checked;
+use crate::error::AocError;
 
 #[derive(Debug, Clone)]
-struct SpringRecord {
-    conditions: String,
-    stats: Vec<u32>,
+pub struct SpringRecord {
+    pub conditions: String,
+    pub stats: Vec<u32>,
 }
 
-#[derive(Hash)]
-struct Payload<'a> {
-    conditions: &'a str,
-    last_pos: &'a Option<usize>,
-}
+// Any unsigned type that can hold arrangement counts: u32, u64, u128 or BigUint
+pub trait ArrangementCount: Clone + Zero + One + CheckedAdd + Display + PartialOrd {}
 
-struct MemoCompute {
-    cache: HashMap<u64, u32>,
-    unknown_positions: Vec<usize>,
-    stats: Vec<u32>,
+impl<T: Clone + Zero + One + CheckedAdd + Display + PartialOrd> ArrangementCount for T {}
+
+// Counts how many ways the rest of the record can be filled in, indexed by
+// position in the conditions and by the next damaged group to place.
+// Used to walk only the branches that lead to a valid arrangement.
+pub struct ArrangementTable<T = u64> {
+    conditions: Vec<u8>,
+    stats: Vec<usize>,
+    ways: Vec<Vec<T>>,
 }
 
-impl MemoCompute {
-    fn new(record: &SpringRecord) -> Self {
-        // Find "?" positions
-        let mut unknown_positions: Vec<usize> = Vec::new();
-        for (i, ch) in record.conditions.chars().enumerate() {
-            if ch == '?' {
-                unknown_positions.push(i);
+impl<T: ArrangementCount> ArrangementTable<T> {
+    pub fn new(record: &SpringRecord) -> Result<Self, AocError> {
+        let conditions: Vec<u8> = record.conditions.bytes().collect();
+        let stats: Vec<usize> = record.stats.iter().map(|x| *x as usize).collect();
+        let mut ways: Vec<Vec<T>> = vec![vec![T::zero(); stats.len() + 1]; conditions.len() + 1];
+
+        // Only an empty tail with all groups placed is a valid arrangement
+        ways[conditions.len()][stats.len()] = T::one();
+
+        for pos in (0..conditions.len()).rev() {
+            for group in 0..=stats.len() {
+                let mut count = T::zero();
+                if conditions[pos] != b'#' {
+                    count = checked::add(count, ways[pos + 1][group].clone(), "arrangement count")?;
+                }
+                if let Some(next) = find_block_end(&conditions, &stats, pos, group) {
+                    count = checked::add(count, ways[next][group + 1].clone(), "arrangement count")?;
+                }
+                ways[pos][group] = count;
             }
         }
 
-        MemoCompute {
-            cache: HashMap::new(),
-            unknown_positions,
-            stats: record.stats.clone(),
-        }
+        Ok(ArrangementTable {
+            conditions,
+            stats,
+            ways,
+        })
     }
 
-    fn count_arrangements(&mut self, conditions: &str, last_pos: Option<usize>) -> u32 {
-        // Replace each unknown condition with either "#" or "." from right to left
-        // and check if it matches the stats.
-        // Do this recursively and count 1 when it matches
-        if let Some(pos) = last_pos {
-            let index = self.unknown_positions[pos];
-
-            // Prevent overflow
-            let mut next_pos = None;
-            if pos > 0 {
-                next_pos = Some(pos - 1);
-            }
-
-            let mut conditions_copy1 = conditions.to_string();
-            let mut conditions_copy2 = conditions.to_string();
+    pub fn count(&self) -> T {
+        self.ways[0][0].clone()
+    }
+}
 
-            // Test for when it is damaged
-            conditions_copy1.replace_range(index..index+1, "#");
+impl<T: ArrangementCount + SampleUniform> ArrangementTable<T> {
+    // Picks one arrangement uniformly by choosing each branch with
+    // a probability proportional to the arrangements below it
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<String> {
+        if self.count().is_zero() {
+            return None;
+        }
 
-            let def_count: u32;
-            let def_tail = &conditions_copy1[index..];
-            let def_cache_key = create_payload_key(def_tail, &next_pos);
-            if let Some(def_count_cached) = self.cache.get(&def_cache_key) {
-                println!("cache hit... {} = {}", def_cache_key, def_count_cached);
-                def_count = *def_count_cached;
+        let mut row = String::with_capacity(self.conditions.len());
+        let mut pos: usize = 0;
+        let mut group: usize = 0;
+
+        while pos < self.conditions.len() {
+            let working = match self.conditions[pos] {
+                b'#' => T::zero(),
+                _ => self.ways[pos + 1][group].clone(),
+            };
+            if rng.gen_range(T::zero()..self.ways[pos][group].clone()) < working {
+                row.push('.');
+                pos += 1;
             } else {
-                def_count = self.count_arrangements(conditions_copy1.as_str(),next_pos);
-                println!("setting cache: {} = {}, {}", def_cache_key, def_count, def_tail);
-                self.cache.insert(def_cache_key, def_count);
+                let next = find_block_end(&self.conditions, &self.stats, pos, group)
+                    .expect("Damaged branch should be valid.");
+                push_block(&mut row, self.stats[group], next - pos);
+                pos = next;
+                group += 1;
             }
+        }
 
-            // Test for when it is operational
-            conditions_copy2.replace_range(index..index+1, ".");
+        Some(row)
+    }
+}
 
-            let working_count: u32;
-            let working_tail = &conditions_copy2[index..];
-            let working_cache_key = create_payload_key(working_tail, &next_pos);
-            if let Some(working_count_cached) = self.cache.get(&working_cache_key) {
-                println!("cache hit... {} = {}", working_cache_key, working_count_cached);
-                working_count = *working_count_cached;
-            } else {
-                working_count = self.count_arrangements(conditions_copy2.as_str(), next_pos);
-                self.cache.insert(working_cache_key, working_count);
-            }
+// Lazily walks every valid arrangement of a record, depth first.
+// Branches with no arrangements are never pushed so each item is found
+// without backtracking. Counts are kept as BigUint so heavily unfolded
+// records do not overflow.
+pub struct Arrangements {
+    table: ArrangementTable<BigUint>,
+    stack: Vec<(usize, usize, String)>,
+    remaining: Option<usize>,
+}
+
+impl Iterator for Arrangements {
+    type Item = String;
 
-            return def_count + working_count;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == Some(0) {
+            return None;
         }
 
-        // If there are no unknown positions, just test it if it matches the stats
-        if compare_arrangement(conditions, &self.stats) {
-            return 1;
+        while let Some((pos, group, row)) = self.stack.pop() {
+            if pos == self.table.conditions.len() {
+                if let Some(remaining) = self.remaining.as_mut() {
+                    *remaining -= 1;
+                }
+                return Some(row);
+            }
+
+            // Push damaged first so that operational springs come out first
+            if let Some(next) = find_block_end(&self.table.conditions, &self.table.stats, pos, group) {
+                if !self.table.ways[next][group + 1].is_zero() {
+                    let mut damaged = row.clone();
+                    push_block(&mut damaged, self.table.stats[group], next - pos);
+                    self.stack.push((next, group + 1, damaged));
+                }
+            }
+
+            if self.table.conditions[pos] != b'#' && !self.table.ways[pos + 1][group].is_zero() {
+                let mut working = row;
+                working.push('.');
+                self.stack.push((pos + 1, group, working));
+            }
         }
-        return 0;
+
+        None
     }
 }
 
-pub fn part1(input: &str) -> u32 {
-    let report = parse_input(input);
-    report.iter().progress().map(|record| {
-        get_arrangement_counts_memo(record)
-    }).sum()
-}
+pub fn arrangements(record: &SpringRecord, limit: Option<usize>) -> Arrangements {
+    let table: ArrangementTable<BigUint> = ArrangementTable::new(record).expect("BigUint counts should not overflow.");
+    let mut stack: Vec<(usize, usize, String)> = Vec::new();
+    if !table.count().is_zero() {
+        stack.push((0, 0, String::with_capacity(table.conditions.len())));
+    }
 
-pub fn part2(input: &str) -> u32 {
-    let report = parse_input(input);
-    report.iter().progress().map(|record| {
-        get_arrangement_counts_memo(record)
-    }).sum()
+    Arrangements {
+        table,
+        stack,
+        remaining: limit,
+    }
 }
 
-fn compare_arrangement(record: &str, stats: &Vec<u32>) -> bool {
-    // Check whether the record matches the given stats
-    // Remove any outer "." character so that we can simply use a separated by list
-    let line = record.trim_matches('.');
-    if let Ok((_, damaged)) = parse_damaged(line) {
-        if damaged.len() == stats.len() {
-            let count: u32 = damaged.iter().zip(stats).map(|(str, stat)| {
-                if str.len() == *stat as usize {
-                    return 1;
+// Works out which unknown conditions are forced by the stats, the same way
+// a nonogram line solver would. Returns None when no arrangement fits.
+pub fn solve_line(record: &SpringRecord) -> Option<String> {
+    let table: ArrangementTable<BigUint> = ArrangementTable::new(record).expect("BigUint counts should not overflow.");
+    if table.count().is_zero() {
+        return None;
+    }
+
+    let conditions = &table.conditions;
+    let stats = &table.stats;
+    let mut can_damaged: Vec<bool> = vec![false; conditions.len()];
+    let mut can_working: Vec<bool> = vec![false; conditions.len()];
+
+    // States reachable from the start that still lead to a valid arrangement
+    let mut live: Vec<Vec<bool>> = vec![vec![false; stats.len() + 1]; conditions.len() + 1];
+    live[0][0] = true;
+
+    for pos in 0..conditions.len() {
+        for group in 0..=stats.len() {
+            if !live[pos][group] {
+                continue;
+            }
+            if conditions[pos] != b'#' && !table.ways[pos + 1][group].is_zero() {
+                can_working[pos] = true;
+                live[pos + 1][group] = true;
+            }
+            if let Some(next) = find_block_end(conditions, stats, pos, group) {
+                if !table.ways[next][group + 1].is_zero() {
+                    let end = pos + stats[group];
+                    can_damaged[pos..end].iter_mut().for_each(|x| *x = true);
+                    if next > end {
+                        can_working[end] = true;
+                    }
+                    live[next][group + 1] = true;
                 }
-                return 0;
-            }).sum();
-            return count == damaged.len() as u32;
+            }
         }
     }
-    false
-}
 
-fn get_arrangement_counts(record: &SpringRecord) -> u32 {
-    // Find "?" positions
-    let mut unknown_positions: Vec<usize> = Vec::new();
-    for (i, ch) in record.conditions.chars().enumerate() {
-        if ch == '?' {
-            unknown_positions.push(i);
+    Some(can_damaged.iter().zip(can_working.iter()).map(|(damaged, working)| {
+        match (damaged, working) {
+            (true, false) => '#',
+            (false, true) => '.',
+            _ => '?',
         }
-    }
+    }).collect())
+}
 
-    let mut last_pos: Option<usize> = None;
-    if unknown_positions.len() > 0 {
-        last_pos = Some(unknown_positions.len() - 1);
-    }
+pub fn sample_arrangements<R: Rng + ?Sized>(record: &SpringRecord, samples: usize, rng: &mut R) -> Vec<String> {
+    let table: ArrangementTable<BigUint> = ArrangementTable::new(record).expect("BigUint counts should not overflow.");
+    (0..samples).filter_map(|_| table.sample(rng)).collect()
+}
 
-    // Create a caching mechanism for partial calculations
-    // Find a way to pass it around and allow immutable read and mutable write
-    let cache: Rc<RefCell<HashMap<u64, u32>>> = Rc::new(RefCell::new(HashMap::new()));
+pub fn count_arrangements<T: ArrangementCount>(record: &SpringRecord) -> Result<T, AocError> {
+    ArrangementTable::<T>::new(record).map(|table| table.count())
+}
 
-    get_arrangement_counts_inner(&cache, record.conditions.as_str(), &unknown_positions, last_pos, &record.stats)
+// Arrangement counts for the record unfolded 1 to max_repeat times
+pub fn arrangement_growth<T: ArrangementCount>(
+    record: &SpringRecord,
+    max_repeat: usize,
+    separator: char,
+) -> Result<Vec<T>, AocError> {
+    (1..=max_repeat).map(|repeat| {
+        count_arrangements(&unfold_record(record, repeat, separator))
+    }).collect()
 }
 
-fn get_arrangement_counts_memo(record: &SpringRecord) -> u32 {
-    let conditions = record.conditions.clone();
-    let mut memo = MemoCompute::new(record);
-    let mut last_pos: Option<usize> = None;
-    if memo.unknown_positions.len() > 0 {
-        last_pos = Some(memo.unknown_positions.len() - 1);
+// Repeats the conditions joined by the separator and repeats the stats to match
+pub fn unfold_record(record: &SpringRecord, repeat: usize, separator: char) -> SpringRecord {
+    SpringRecord {
+        conditions: vec![record.conditions.as_str(); repeat].join(separator.to_string().as_str()),
+        stats: record.stats.repeat(repeat),
     }
-
-    memo.count_arrangements(&conditions, last_pos)
 }
 
-fn get_arrangement_counts_inner(
-    cache: &Rc<RefCell<HashMap<u64, u32>>>,
-    conditions: &str,
-    unknown_positions: &Vec<usize>,
-    last_pos: Option<usize>,
-    stats: &Vec<u32>,
-) -> u32 {
-    // Replace each unknown condition with either "#" or "." from right to left
-    // and check if it matches the stats.
-    // Do this recursively and count 1 when it matches
-    if let Some(pos) = last_pos {
-        let index = unknown_positions[pos];
-
-        // Prevent overflow
-        let mut next_pos = None;
-        if pos > 0 {
-            next_pos = Some(pos - 1);
-        }
-
-        let mut conditions_copy1 = conditions.to_string();
-        let mut conditions_copy2 = conditions.to_string();
+pub fn part1(input: &str) -> Result<u64, AocError> {
+    total_arrangements(&parse_input(input), 1)
+}
 
-        // Test for when it is damaged
-        conditions_copy1.replace_range(index..index+1, "#");
+pub fn part2(input: &str) -> Result<u128, AocError> {
+    total_arrangements(&parse_input(input), 5)
+}
 
-        let def_count: u32;
-        let def_tail = &conditions_copy1[index..];
-        let def_cache_key = create_payload_key(def_tail, &next_pos);
-        if let Some(def_count_cached) = cache.borrow().get(&def_cache_key) {
-            def_count = *def_count_cached;
-        } else {
-            def_count = get_arrangement_counts_inner(cache, conditions_copy1.as_str(), unknown_positions, next_pos, stats);
-            cache.borrow_mut().insert(def_cache_key, def_count);
-        }
+// Sum of the arrangements of every record, after unfolding it repeat times
+pub fn total_arrangements<T: ArrangementCount>(report: &[SpringRecord], repeat: usize) -> Result<T, AocError> {
+    let counts = report.iter()
+        .map(|record| count_arrangements::<T>(&unfold_record(record, repeat, '?')))
+        .collect::<Result<Vec<T>, AocError>>()?;
+    checked::sum(counts, "total arrangements")
+}
 
-        // Test for when it is operational
-        conditions_copy2.replace_range(index..index+1, ".");
-
-        let working_count: u32;
-        let working_tail = &conditions_copy2[index..];
-        let working_cache_key = create_payload_key(working_tail, &next_pos);
-        if let Some(working_count_cached) = cache.borrow().get(&working_cache_key) {
-            working_count = *working_count_cached;
-        } else {
-            working_count = get_arrangement_counts_inner(cache, conditions_copy2.as_str(), unknown_positions, next_pos, stats);
-            cache.borrow_mut().insert(working_cache_key, working_count);
+pub fn compare_arrangement(record: &str, stats: &[u32]) -> bool {
+    // Check whether the record matches the given stats
+    // Remove any outer "." character so that we can simply use a separated by list
+    let line = record.trim_matches('.');
+    if let Ok((_, damaged)) = parse_damaged(line) {
+        if damaged.len() == stats.len() {
+            return damaged.iter().zip(stats).all(|(str, stat)| str.len() == *stat as usize);
         }
-
-        return def_count + working_count;
     }
+    false
+}
 
-    // If there are no unknown positions, just test it if it matches the stats
-    if compare_arrangement(conditions, stats) {
-        return 1;
+// Returns the position after placing the damaged group at the given position,
+// including the operational spring that must follow it
+fn find_block_end(conditions: &[u8], stats: &[usize], pos: usize, group: usize) -> Option<usize> {
+    let len = *stats.get(group)?;
+    let end = pos + len;
+    if end > conditions.len() || conditions[pos..end].contains(&b'.') {
+        return None;
+    }
+    match conditions.get(end) {
+        None => Some(end),
+        Some(b'#') => None,
+        Some(_) => Some(end + 1),
     }
-    return 0;
 }
 
-fn create_payload_key(
-    conditions: &str,
-    last_pos: &Option<usize>,
-) -> u64 {
-    let mut usher = DefaultHasher::new();
-    let payload = Payload {
-        conditions,
-        last_pos,
-    };
-
-    payload.hash(&mut usher);
-    usher.finish()
+fn push_block(row: &mut String, len: usize, width: usize) {
+    row.extend(std::iter::repeat_n('#', len));
+    if width > len {
+        row.push('.');
+    }
 }
 
 fn dots(line: &str) -> IResult<&str, &str> {
@@ -250,7 +296,7 @@ fn parse_damaged(line: &str) -> IResult<&str, Vec<&str>> {
     separated_list1(dots, sharps)(line)
 }
 
-fn parse_input(input: &str) -> Vec<SpringRecord> {
+pub fn parse_input(input: &str) -> Vec<SpringRecord> {
     input.lines().map(|line| {
         let (conditions, stats) = line.split_once(" ").expect("Should have two parts.");
         SpringRecord {
@@ -263,106 +309,143 @@ fn parse_input(input: &str) -> Vec<SpringRecord> {
     }).collect::<Vec<SpringRecord>>()
 }
 
-fn parse_input_unfolded(input: &str) -> Vec<SpringRecord> {
-    input.lines().map(|line| {
-        let (conditions, stats) = line.split_once(" ").expect("Should have two parts.");
-        let mut unfolded_conditions: Vec<&str> = Vec::new();
-        let mut unfolded_stats: Vec<&str> = Vec::new();
-
-        for _ in 0..5 {
-            unfolded_conditions.push(conditions);
-            unfolded_stats.push(stats);
-        }
-
-        let u_conditions = unfolded_conditions.join("?");
-        let u_stats = unfolded_stats.join(",");
-
-        SpringRecord {
-            conditions: u_conditions,
-            stats: u_stats.split(',').map(|x| {
-                let num: u32 = x.to_string().parse::<u32>().expect("Stats should be numeric.");
-                num
-            }).collect(),
-        }
+pub fn parse_input_unfolded(input: &str, repeat: usize, separator: char) -> Vec<SpringRecord> {
+    parse_input(input).iter().map(|record| {
+        unfold_record(record, repeat, separator)
     }).collect::<Vec<SpringRecord>>()
 }
 
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
     use super::*;
+    use crate::examples::example_input;
+
+    crate::example_tests!(12);
 
     #[test]
     fn test_part1_compare_arrangements() {
-        assert!(compare_arrangement("#.#.###", &vec![1, 1, 3]));
-        assert!(compare_arrangement(".#...#....###.", &vec![1, 1, 3]));
-        assert!(compare_arrangement(".#.###.#.######", &vec![1, 3, 1, 6]));
-        assert!(compare_arrangement("####.#...#...", &vec![4, 1, 1]));
-        assert!(compare_arrangement("#....######..#####.", &vec![1, 6, 5]));
-        assert!(compare_arrangement(".###.##....#", &vec![3, 2, 1]));
+        assert!(compare_arrangement("#.#.###", &[1, 1, 3]));
+        assert!(compare_arrangement(".#...#....###.", &[1, 1, 3]));
+        assert!(compare_arrangement(".#.###.#.######", &[1, 3, 1, 6]));
+        assert!(compare_arrangement("####.#...#...", &[4, 1, 1]));
+        assert!(compare_arrangement("#....######..#####.", &[1, 6, 5]));
+        assert!(compare_arrangement(".###.##....#", &[3, 2, 1]));
     }
 
-
     #[test]
     fn test_part1_arrangements() {
-        let input = "???.### 1,1,3
-.??..??...?##. 1,1,3
-?#?#?#?#?#?#?#? 1,3,1,6
-????.#...#... 4,1,1
-????.######..#####. 1,6,5
-?###???????? 3,2,1";
+        let input = example_input(12, "records");
 
-        let report = parse_input(input);
+        let report = parse_input(&input);
         assert_eq!(report.len(), 6);
-        assert_eq!(get_arrangement_counts_memo(&report[0]), 1);
-        assert_eq!(get_arrangement_counts_memo(&report[1]), 4);
-        assert_eq!(get_arrangement_counts_memo(&report[2]), 1);
-        assert_eq!(get_arrangement_counts_memo(&report[3]), 1);
-        assert_eq!(get_arrangement_counts_memo(&report[4]), 4);
-        assert_eq!(get_arrangement_counts_memo(&report[5]), 10);
+        assert_eq!(count_arrangements::<u32>(&report[0]), Ok(1));
+        assert_eq!(count_arrangements::<u32>(&report[1]), Ok(4));
+        assert_eq!(count_arrangements::<u32>(&report[2]), Ok(1));
+        assert_eq!(count_arrangements::<u32>(&report[3]), Ok(1));
+        assert_eq!(count_arrangements::<u32>(&report[4]), Ok(4));
+        assert_eq!(count_arrangements::<u32>(&report[5]), Ok(10));
     }
 
     #[test]
-    fn test_part1() {
-        let input = "???.### 1,1,3
-.??..??...?##. 1,1,3
-?#?#?#?#?#?#?#? 1,3,1,6
-????.#...#... 4,1,1
-????.######..#####. 1,6,5
-?###???????? 3,2,1";
+    fn test_part1_arrangement_table() {
+        let input = example_input(12, "records");
 
-        let result = part1(input);
-        assert_eq!(result, 21);
+        let counts: Vec<u64> = parse_input(&input).iter().map(|record| {
+            ArrangementTable::new(record).unwrap().count()
+        }).collect();
+        assert_eq!(counts, vec![1, 4, 1, 1, 4, 10]);
     }
 
-    //#[test]
-    fn test_part2_arrangements() {
-        let input = "???.### 1,1,3
+    #[test]
+    fn test_enumerate_arrangements() {
+        let report = parse_input("?###???????? 3,2,1");
+        let rows: Vec<String> = arrangements(&report[0], None).collect();
+        assert_eq!(rows.len(), 10);
+        assert_eq!(rows[0], ".###....##.#");
+        assert_eq!(rows.iter().unique().count(), 10);
+        for row in rows.iter() {
+            assert_eq!(row.len(), report[0].conditions.len());
+            assert!(compare_arrangement(row, &report[0].stats));
+            assert!(row.chars().zip(report[0].conditions.chars()).all(|(a, b)| b == '?' || a == b));
+        }
+
+        assert_eq!(arrangements(&report[0], Some(3)).count(), 3);
+        assert_eq!(arrangements(&parse_input("#.# 2")[0], None).count(), 0);
+    }
+
+    #[test]
+    fn test_sample_arrangements() {
+        let report = parse_input(".??..??...?##. 1,1,3");
+        // Seeded so the roughly-a-quarter check below cannot fail by chance
+        let mut rng = StdRng::seed_from_u64(2023);
+        let samples = sample_arrangements(&report[0], 400, &mut rng);
+        assert_eq!(samples.len(), 400);
+
+        let all: Vec<String> = arrangements(&report[0], None).collect();
+        let counts = samples.iter().counts();
+        assert_eq!(counts.len(), all.len());
+        for row in all.iter() {
+            // Each of the 4 arrangements should get roughly a quarter
+            assert!(counts[row] > 50);
+        }
+    }
+
+    #[test]
+    fn test_solve_line() {
+        let report = parse_input("?###???????? 3,2,1
+???.### 1,1,3
 .??..??...?##. 1,1,3
-?#?#?#?#?#?#?#? 1,3,1,6
-????.#...#... 4,1,1
-????.######..#####. 1,6,5
-?###???????? 3,2,1";
+????????? 5
+???#??.## 3,2
+#.# 2");
+        assert_eq!(solve_line(&report[0]), Some(".###.???????".to_string()));
+        assert_eq!(solve_line(&report[1]), Some("#.#.###".to_string()));
+        assert_eq!(solve_line(&report[2]), Some(".??..??...###.".to_string()));
+        assert_eq!(solve_line(&report[3]), Some("????#????".to_string()));
+        assert_eq!(solve_line(&report[4]), Some(".??#??.##".to_string()));
+        assert_eq!(solve_line(&report[5]), None);
+    }
+
+    #[test]
+    fn test_part2_arrangements() {
+        let input = example_input(12, "records");
 
-        let report = parse_input_unfolded(input);
+        let report = parse_input_unfolded(&input, 5, '?');
         assert_eq!(report.len(), 6);
-        assert_eq!(get_arrangement_counts_memo(&report[0]), 1);
-        assert_eq!(get_arrangement_counts_memo(&report[1]), 16384);
-        assert_eq!(get_arrangement_counts_memo(&report[2]), 1);
-        assert_eq!(get_arrangement_counts_memo(&report[3]), 4);
-        assert_eq!(get_arrangement_counts_memo(&report[4]), 2500);
-        assert_eq!(get_arrangement_counts_memo(&report[5]), 506250);
+        assert_eq!(report[0].conditions, "???.###????.###????.###????.###????.###");
+        assert_eq!(report[0].stats.len(), 15);
+        assert_eq!(count_arrangements::<u64>(&report[0]), Ok(1));
+        assert_eq!(count_arrangements::<u64>(&report[1]), Ok(16384));
+        assert_eq!(count_arrangements::<u64>(&report[2]), Ok(1));
+        assert_eq!(count_arrangements::<u64>(&report[3]), Ok(16));
+        assert_eq!(count_arrangements::<u64>(&report[4]), Ok(2500));
+        assert_eq!(count_arrangements::<u64>(&report[5]), Ok(506250));
     }
 
     #[test]
-    fn test_part2() {
-        let input = "???.### 1,1,3
-.??..??...?##. 1,1,3
-?#?#?#?#?#?#?#? 1,3,1,6
-????.#...#... 4,1,1
-????.######..#####. 1,6,5
-?###???????? 3,2,1";
+    fn test_unfold_separator() {
+        let report = parse_input_unfolded("?#? 1", 3, '.');
+        assert_eq!(report[0].conditions, "?#?.?#?.?#?");
+        assert_eq!(report[0].stats, vec![1, 1, 1]);
+        assert_eq!(count_arrangements::<u32>(&report[0]), Ok(1));
+    }
 
-        let result = part2(input);
-        assert_eq!(result, 525152);
+    #[test]
+    fn test_arrangement_growth() {
+        let report = parse_input("?###???????? 3,2,1");
+        let growth: Vec<u128> = arrangement_growth(&report[0], 5, '?').unwrap();
+        assert_eq!(growth, vec![10, 150, 2250, 33750, 506250]);
+
+        // Way past what fits in a u128
+        let big: BigUint = count_arrangements(&unfold_record(&report[0], 40, '?')).unwrap();
+        assert_eq!(big, BigUint::from(10u32) * BigUint::from(15u32).pow(39));
+        if checked::ENABLED {
+            let unfolded = unfold_record(&report[0], 40, '?');
+            assert!(matches!(count_arrangements::<u128>(&unfolded), Err(AocError::Overflow { .. })));
+        }
     }
+
 }
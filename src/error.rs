@@ -0,0 +1,17 @@
+use std::fmt;
+
+// Failures a solution reports instead of returning a wrong answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AocError {
+    Overflow { context: String },
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AocError::Overflow { context } => write!(f, "arithmetic overflow in {}", context),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+
+use crate::answers::{answer_value, summary_table, verify_part, Verification};
+use crate::registry::{find_solution, Solution};
+
+// Puzzle examples with the answers given in the puzzle text, one file per day
+// under data/examples/. They are embedded so the runner works from anywhere.
+// `aoc new` appends new days to this list.
+const EXAMPLE_FILES: &[(u32, &str)] = &[
+    (1, include_str!("../data/examples/day01.toml")),
+    (2, include_str!("../data/examples/day02.toml")),
+    (3, include_str!("../data/examples/day03.toml")),
+    (4, include_str!("../data/examples/day04.toml")),
+    (5, include_str!("../data/examples/day05.toml")),
+    (6, include_str!("../data/examples/day06.toml")),
+    (7, include_str!("../data/examples/day07.toml")),
+    (8, include_str!("../data/examples/day08.toml")),
+    (9, include_str!("../data/examples/day09.toml")),
+    (10, include_str!("../data/examples/day10.toml")),
+    (11, include_str!("../data/examples/day11.toml")),
+    (12, include_str!("../data/examples/day12.toml")),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub day: u32,
+    pub name: String,
+    pub input: String,
+    pub answers: BTreeMap<u32, String>,
+}
+
+// Each file holds a list of examples like:
+//
+// [[example]]
+// name = "races"
+// part1 = 288
+// input = '''
+// Time:      7  15   30
+// Distance:  9  40  200'''
+//
+// An example only lists the parts the puzzle gives an answer for.
+pub fn parse_examples(day: u32, content: &str) -> Result<Vec<Example>, String> {
+    let table: toml::Table = content.parse().map_err(|err: toml::de::Error| err.to_string())?;
+    let entries = match table.get("example") {
+        None => return Ok(Vec::new()),
+        Some(entries) => entries.as_array().ok_or("example should be an array of tables")?,
+    };
+
+    entries.iter().map(|entry| {
+        let entry = entry.as_table().ok_or("example should be a table")?;
+        let name = entry.get("name").and_then(|name| name.as_str()).ok_or("example should have a name")?;
+        let input = entry.get("input").and_then(|input| input.as_str())
+            .ok_or(format!("example {} should have an input", name))?;
+
+        let mut answers = BTreeMap::new();
+        for part in 1..=2 {
+            if let Some(value) = entry.get(format!("part{}", part).as_str()) {
+                let answer = answer_value(value)
+                    .ok_or(format!("example {}: part{} should be an integer or a string", name, part))?;
+                answers.insert(part, answer);
+            }
+        }
+
+        Ok(Example { day, name: name.to_string(), input: input.to_string(), answers })
+    }).collect()
+}
+
+pub fn examples(day: u32) -> Vec<Example> {
+    EXAMPLE_FILES.iter()
+        .filter(|(file_day, _)| *file_day == day)
+        .flat_map(|(_, content)| parse_examples(day, content).expect("Example files should be valid."))
+        .collect()
+}
+
+pub fn example_input(day: u32, name: &str) -> String {
+    examples(day).into_iter()
+        .find(|example| example.name == name)
+        .map(|example| example.input)
+        .unwrap_or_else(|| panic!("Day {} should have an example named {}", day, name))
+}
+
+// Runs the solution on every example of its day, only for the parts with an answer
+pub fn run_examples(solution: &Solution) -> Vec<Verification> {
+    let mut verifications = Vec::new();
+
+    for example in examples(solution.day).iter() {
+        for (part, expected) in example.answers.iter() {
+            let run = solution.part(*part).expect("Examples should only have answers for two parts.");
+            verifications.push(verify_part(
+                solution.day,
+                *part,
+                format!("example {}", example.name),
+                || run(example.input.as_str()),
+                Some(expected.as_str()),
+            ));
+        }
+    }
+
+    verifications
+}
+
+pub fn assert_examples(day: u32) {
+    let solution = find_solution(day).expect("Day should be registered.");
+    let verifications = run_examples(&solution);
+    assert!(!verifications.is_empty(), "Day {} has no examples", day);
+    assert!(!verifications.iter().any(|v| v.is_failure()), "\n{}", summary_table(&verifications));
+}
+
+// Generates a test that checks every example of the day against its answers
+#[macro_export]
+macro_rules! example_tests {
+    ($day:expr) => {
+        #[test]
+        fn test_examples() {
+            $crate::examples::assert_examples($day);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_examples() {
+        let content = "[[example]]
+name = \"first\"
+part1 = 1
+input = '''
+a
+b'''
+
+[[example]]
+name = \"second\"
+part2 = \"x\"
+input = 'c'";
+
+        let parsed = parse_examples(3, content).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].input, "a\nb");
+        assert_eq!(parsed[0].answers, BTreeMap::from([(1, "1".to_string())]));
+        assert_eq!(parsed[1].answers, BTreeMap::from([(2, "x".to_string())]));
+
+        assert!(parse_examples(3, "[[example]]\ninput = 'a'").is_err());
+        assert!(parse_examples(3, "[[example]]\nname = 'a'\ninput = 'a'\npart1 = 1.5").is_err());
+    }
+
+    #[test]
+    fn test_example_files() {
+        for (day, content) in EXAMPLE_FILES.iter() {
+            let parsed = parse_examples(*day, content).unwrap();
+            assert!(parsed.iter().all(|example| !example.answers.is_empty()));
+        }
+        assert_eq!(example_input(6, "races"), "Time:      7  15   30\nDistance:  9  40  200");
+    }
+}
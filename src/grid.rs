@@ -0,0 +1,244 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+// Offsets as (row, col), clockwise starting north
+pub const NEIGHBOURS4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+pub const NEIGHBOURS8: [(isize, isize); 8] = [(-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1)];
+
+// A rectangular grid stored row by row in a single Vec. Positions are
+// (row, col) with (0, 0) in the top left corner, so that they read the same
+// way as the puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    Ragged { row: usize, expected: usize, found: usize },
+    UnexpectedChar { position: (usize, usize), ch: char },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::Ragged { row, expected, found } => {
+                write!(f, "row {} has {} cells, expected {}", row, found, expected)
+            },
+            GridError::UnexpectedChar { position: (row, col), ch } => {
+                write!(f, "unexpected character {:?} at row {}, col {}", ch, row, col)
+            },
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(cells.len(), rows * cols, "A {}x{} grid needs {} cells.", rows, cols, rows * cols);
+        Grid { cells, rows, cols }
+    }
+
+    pub fn filled(rows: usize, cols: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::new(rows, cols, vec![value; rows * cols])
+    }
+
+    // One line per row, every character mapped to a cell. `to_cell` returns
+    // None for characters that do not belong in the map.
+    pub fn parse<F: FnMut(char) -> Option<T>>(input: &str, mut to_cell: F) -> Result<Grid<T>, GridError> {
+        let mut cells: Vec<T> = Vec::new();
+        let mut rows: usize = 0;
+        let mut cols: usize = 0;
+
+        for (row, line) in input.lines().enumerate() {
+            let start = cells.len();
+            for (col, ch) in line.chars().enumerate() {
+                let cell = to_cell(ch).ok_or(GridError::UnexpectedChar { position: (row, col), ch })?;
+                cells.push(cell);
+            }
+
+            let found = cells.len() - start;
+            if row == 0 {
+                cols = found;
+            } else if found != cols {
+                return Err(GridError::Ragged { row, expected: cols, found });
+            }
+            rows += 1;
+        }
+
+        Ok(Grid { cells, rows, cols })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, (row, col): (usize, usize)) -> bool {
+        row < self.rows && col < self.cols
+    }
+
+    pub fn get(&self, position: (usize, usize)) -> Option<&T> {
+        self.contains(position).then(|| &self.cells[position.0 * self.cols + position.1])
+    }
+
+    pub fn get_mut(&mut self, position: (usize, usize)) -> Option<&mut T> {
+        if self.contains(position) {
+            Some(&mut self.cells[position.0 * self.cols + position.1])
+        } else {
+            None
+        }
+    }
+
+    // The position (dr, dc) away, if it is still inside the grid
+    pub fn step(&self, (row, col): (usize, usize), (dr, dc): (isize, isize)) -> Option<(usize, usize)> {
+        let next = (row.checked_add_signed(dr)?, col.checked_add_signed(dc)?);
+        self.contains(next).then_some(next)
+    }
+
+    pub fn neighbours4(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS4.iter().filter_map(move |offset| self.step(position, *offset))
+    }
+
+    pub fn neighbours8(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS8.iter().filter_map(move |offset| self.step(position, *offset))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(col < self.cols, "Column {} is outside the grid.", col);
+        self.cells.iter().skip(col).step_by(self.cols.max(1))
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.rows).map(|row| self.row(row))
+    }
+
+    // Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.cells.iter().enumerate().map(|(i, cell)| ((i / self.cols, i % self.cols), cell))
+    }
+
+    pub fn position<F: FnMut(&T) -> bool>(&self, mut predicate: F) -> Option<(usize, usize)> {
+        self.iter().find(|(_, cell)| predicate(cell)).map(|(position, _)| position)
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid::new(self.rows, self.cols, self.cells.iter().map(f).collect())
+    }
+
+    // Rows become columns, mirroring the grid along its main diagonal
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..self.cols).flat_map(|col| self.column(col).cloned()).collect();
+        Grid::new(self.cols, self.rows, cells)
+    }
+
+    // Rotates a quarter turn clockwise, the first column becomes the first row
+    // read bottom to top
+    pub fn rotate(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..self.cols).flat_map(|col| {
+            (0..self.rows).rev().map(move |row| self[(row, col)].clone())
+        }).collect();
+        Grid::new(self.cols, self.rows, cells)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &T {
+        self.get(position).unwrap_or_else(|| panic!("Position {:?} is outside the grid.", position))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut T {
+        self.get_mut(position).unwrap_or_else(|| panic!("Position {:?} is outside the grid.", position))
+    }
+}
+
+// Prints one line per row, which gives back the char map for cells that
+// display as the character they were parsed from
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, row) in self.iter_rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row.iter() {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters() -> Grid<char> {
+        Grid::parse("abc\ndef", Some).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = letters();
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid[(1, 0)], 'd');
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.position(|ch| *ch == 'e'), Some((1, 1)));
+        assert_eq!(grid.to_string(), "abc\ndef");
+
+        let digits = Grid::parse("12\n3x", |ch| ch.to_digit(10));
+        assert_eq!(digits, Err(GridError::UnexpectedChar { position: (1, 1), ch: 'x' }));
+        let ragged = Grid::parse("ab\nc", Some);
+        assert_eq!(ragged, Err(GridError::Ragged { row: 1, expected: 2, found: 1 }));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = letters();
+        assert_eq!(grid.neighbours4((0, 0)).collect::<Vec<_>>(), vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours4((1, 1)).collect::<Vec<_>>(), vec![(0, 1), (1, 2), (1, 0)]);
+        assert_eq!(grid.neighbours8((0, 1)).count(), 5);
+        assert_eq!(grid.step((0, 2), (0, 1)), None);
+        assert_eq!(grid.step((1, 2), (-1, -2)), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_views() {
+        let grid = letters();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(grid.iter_rows().count(), 2);
+        assert_eq!(grid.map(|ch| ch.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+
+    #[test]
+    fn test_transpose_and_rotate() {
+        let grid = letters();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate().rotate().rotate().rotate(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+}
@@ -1,4 +1,9 @@
 
+pub mod answer;
+pub mod answers;
+pub mod batch;
+pub mod bench;
+pub mod checked;
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -12,3 +17,11 @@ pub mod day10;
 pub mod day11;
 pub mod day12;
 pub mod day13;
+pub mod error;
+pub mod examples;
+pub mod grid;
+pub mod nonogram;
+pub mod registry;
+pub mod runner;
+pub mod scaffold;
+pub mod timing;
@@ -0,0 +1,220 @@
+use crate::day12::{solve_line, SpringRecord};
+
+// A nonogram is a grid of spring records: each row and each column has its
+// own damaged groups and optionally some conditions that are already known.
+#[derive(Debug, Clone)]
+pub struct Nonogram {
+    pub rows: Vec<SpringRecord>,
+    pub cols: Vec<SpringRecord>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NonogramSolution {
+    pub grid: Vec<Vec<char>>,
+    pub unique: bool,
+}
+
+impl NonogramSolution {
+    pub fn render(&self) -> String {
+        self.grid.iter().map(|row| row.iter().collect::<String>()).collect::<Vec<String>>().join("\n")
+    }
+}
+
+pub fn solve(puzzle: &Nonogram) -> Option<NonogramSolution> {
+    let grid = create_initial_grid(puzzle)?;
+
+    // Stop looking once a second solution shows up, enough to tell it is not unique
+    let mut solutions: Vec<Vec<Vec<char>>> = Vec::new();
+    search(puzzle, grid, &mut solutions);
+
+    let unique = solutions.len() == 1;
+    solutions.into_iter().next().map(|grid| NonogramSolution { grid, unique })
+}
+
+fn search(puzzle: &Nonogram, mut grid: Vec<Vec<char>>, solutions: &mut Vec<Vec<Vec<char>>>) {
+    if solutions.len() > 1 || !propagate(puzzle, &mut grid) {
+        return;
+    }
+
+    let unknown = grid.iter().enumerate().find_map(|(r, row)| {
+        row.iter().position(|ch| *ch == '?').map(|c| (r, c))
+    });
+
+    match unknown {
+        None => solutions.push(grid),
+        Some((r, c)) => {
+            // Line solving stalled, guess the first unknown cell both ways
+            for guess in ['#', '.'] {
+                let mut next = grid.clone();
+                next[r][c] = guess;
+                search(puzzle, next, solutions);
+            }
+        }
+    }
+}
+
+// Applies the line solver to every row and column until nothing changes.
+// Returns false when some line has no valid arrangement left.
+fn propagate(puzzle: &Nonogram, grid: &mut [Vec<char>]) -> bool {
+    let width = puzzle.cols.len();
+    let height = puzzle.rows.len();
+
+    loop {
+        let mut changed = false;
+
+        for (r, row) in puzzle.rows.iter().enumerate() {
+            let cells: Vec<(usize, usize)> = (0..width).map(|c| (r, c)).collect();
+            match update_line(grid, &cells, &row.stats) {
+                None => return false,
+                Some(updated) => changed |= updated,
+            }
+        }
+
+        for (c, col) in puzzle.cols.iter().enumerate() {
+            let cells: Vec<(usize, usize)> = (0..height).map(|r| (r, c)).collect();
+            match update_line(grid, &cells, &col.stats) {
+                None => return false,
+                Some(updated) => changed |= updated,
+            }
+        }
+
+        if !changed {
+            return true;
+        }
+    }
+}
+
+fn update_line(grid: &mut [Vec<char>], cells: &[(usize, usize)], stats: &[u32]) -> Option<bool> {
+    let record = SpringRecord {
+        conditions: cells.iter().map(|(r, c)| grid[*r][*c]).collect(),
+        stats: stats.to_vec(),
+    };
+    let solved = solve_line(&record)?;
+
+    let mut changed = false;
+    for ((r, c), ch) in cells.iter().zip(solved.chars()) {
+        if grid[*r][*c] != ch {
+            grid[*r][*c] = ch;
+            changed = true;
+        }
+    }
+    Some(changed)
+}
+
+fn create_initial_grid(puzzle: &Nonogram) -> Option<Vec<Vec<char>>> {
+    let mut grid: Vec<Vec<char>> = puzzle.rows.iter().map(|row| row.conditions.chars().collect()).collect();
+
+    // Column conditions must agree with whatever the rows already know
+    for (c, col) in puzzle.cols.iter().enumerate() {
+        for (r, ch) in col.conditions.chars().enumerate() {
+            match (grid[r][c], ch) {
+                (_, '?') => (),
+                ('?', _) => grid[r][c] = ch,
+                (known, _) if known != ch => return None,
+                _ => (),
+            }
+        }
+    }
+
+    Some(grid)
+}
+
+// Row clues come first, then a blank line, then column clues. Each line is a
+// spring record like "??#?? 1,1", or just the groups when nothing is known.
+// A group of 0 marks an empty line.
+pub fn parse_nonogram(input: &str) -> Nonogram {
+    let (rows, cols) = input.trim().split_once("\n\n").expect("Should have row and column clues.");
+    let row_lines: Vec<&str> = rows.lines().collect();
+    let col_lines: Vec<&str> = cols.lines().collect();
+
+    Nonogram {
+        rows: row_lines.iter().map(|line| parse_line(line, col_lines.len())).collect(),
+        cols: col_lines.iter().map(|line| parse_line(line, row_lines.len())).collect(),
+    }
+}
+
+fn parse_line(line: &str, len: usize) -> SpringRecord {
+    let (conditions, stats) = match line.trim().split_once(' ') {
+        Some((conditions, stats)) => (conditions.to_string(), stats),
+        None => ("?".repeat(len), line.trim()),
+    };
+    assert_eq!(conditions.len(), len, "Conditions should cover the whole line.");
+
+    SpringRecord {
+        conditions,
+        stats: stats.split(',')
+            .map(|x| x.parse::<u32>().expect("Stats should be numeric."))
+            .filter(|x| *x > 0)
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_unique() {
+        let input = "1,1
+5
+1
+3
+1,1
+
+2
+1,2
+4
+1,2
+1";
+
+        let solution = solve(&parse_nonogram(input)).expect("Should be solvable.");
+        assert!(solution.unique);
+        assert_eq!(solution.render(), "#.#..
+#####
+..#..
+.###.
+.#.#.");
+    }
+
+    #[test]
+    fn test_solve_with_guessing() {
+        let input = "1
+1
+
+1
+1";
+
+        let solution = solve(&parse_nonogram(input)).expect("Should be solvable.");
+        assert!(!solution.unique);
+
+        // Knowing one cell upfront settles it
+        let input = "#? 1
+1
+
+1
+1";
+
+        let solution = solve(&parse_nonogram(input)).expect("Should be solvable.");
+        assert!(solution.unique);
+        assert_eq!(solution.render(), "#.\n.#");
+    }
+
+    #[test]
+    fn test_solve_impossible() {
+        let input = "2
+2
+
+1
+1";
+
+        assert!(solve(&parse_nonogram(input)).is_none());
+
+        let input = "#. 1
+0
+
+.? 1
+1";
+
+        assert!(solve(&parse_nonogram(input)).is_none());
+    }
+}
@@ -0,0 +1,131 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use num::BigUint;
+
+use crate::answer::{Answer, IntoAnswer};
+use crate::day02::{CubeSet, Game};
+use crate::error::AocError;
+use crate::grid::Grid;
+use crate::{day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13};
+
+// Every solved day with both parts, answers converted to `Answer` so that days
+// with different answer types can be run side by side. A part fails instead
+// of answering when an overflow check trips. `timed` runs a part with the
+// parsing and solving phases timed separately, `parses` tells whether there
+// is a parsing phase at all.
+pub type Part = fn(&str) -> Result<Answer, AocError>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub day: u32,
+    pub part1: Part,
+    pub part2: Part,
+    pub parses: bool,
+    pub timed: fn(&str, u32) -> Option<TimedRun>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimedRun {
+    pub answer: Result<Answer, AocError>,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Solution {
+    pub fn part(&self, part: u32) -> Option<Part> {
+        match part {
+            1 => Some(self.part1),
+            2 => Some(self.part2),
+            _ => None,
+        }
+    }
+
+    pub fn run_timed(&self, input: &str, part: u32) -> Option<TimedRun> {
+        (self.timed)(input, part)
+    }
+}
+
+// Builds a solution from the day's parser and the two solvers that work on
+// whatever the parser returns
+macro_rules! solution {
+    (@build $day:expr, $parses:expr, $parse:expr, $part1:expr, $part2:expr) => {
+        Solution {
+            day: $day,
+            part1: |input| $part1(&$parse(input)).into_answer(),
+            part2: |input| $part2(&$parse(input)).into_answer(),
+            parses: $parses,
+            timed: |input, part| {
+                let start = Instant::now();
+                let parsed = $parse(input);
+                let parse = start.elapsed();
+
+                let start = Instant::now();
+                let answer = match part {
+                    1 => $part1(&parsed).into_answer(),
+                    2 => $part2(&parsed).into_answer(),
+                    _ => return None,
+                };
+                Some(TimedRun { answer, parse, solve: start.elapsed() })
+            },
+        }
+    };
+    ($day:expr, unparsed, $part1:expr, $part2:expr) => {
+        solution!(@build $day, false, unparsed, $part1, $part2)
+    };
+    ($day:expr, $parse:expr, $part1:expr, $part2:expr) => {
+        solution!(@build $day, true, $parse, $part1, $part2)
+    };
+}
+
+// For the days that work straight on the input text
+fn unparsed(input: &str) -> &str {
+    input
+}
+
+// `aoc new` adds new days to the imports above and to the end of this list
+pub fn solutions() -> Vec<Solution> {
+    vec![
+        solution!(1, unparsed, day01::part1, day01::part2),
+        solution!(
+            2,
+            |input| day02::parse_games(input).expect("Games should be valid."),
+            |games: &Vec<Game>| day02::get_total_games(games, &CubeSet::rgb(12, 13, 14)),
+            day02::get_sum_of_power
+        ),
+        solution!(3, day03::Schematic::parse, day03::sum_part_numbers, day03::compute_gears),
+        solution!(
+            4,
+            |input| day04::parse_cards(input).expect("Cards should be valid."),
+            day04::compute_winning_points,
+            day04::count_total_cards::<BigUint>
+        ),
+        solution!(5, day05::parse_input, day05::find_closest_location, day05::find_closest_location_reversed),
+        solution!(6, unparsed, day06::part1, day06::part2),
+        solution!(7, day07::parse_hands, day07::total_winnings, day07::total_winnings_with_jokers),
+        solution!(8, day08::parse_network, day08::count_moves, day08::count_ghost_moves),
+        solution!(9, day09::parse_histories, day09::sum_next_values, day09::sum_prev_values),
+        solution!(10, day10::parse_input, day10::find_farthest_steps, day10::count_enclosed_tiles),
+        solution!(
+            11,
+            day11::parse_input,
+            |sky: &Grid<day11::SkyItem>| day11::sum_shortest_paths(sky, 2),
+            |sky: &Grid<day11::SkyItem>| day11::sum_shortest_paths(sky, 1000000)
+        ),
+        solution!(
+            12,
+            day12::parse_input,
+            |report: &Vec<day12::SpringRecord>| day12::total_arrangements::<u64>(report, 1),
+            |report: &Vec<day12::SpringRecord>| day12::total_arrangements::<u128>(report, 5)
+        ),
+        solution!(13, unparsed, day13::part1, day13::part2),
+    ]
+}
+
+pub fn find_solution(day: u32) -> Option<Solution> {
+    solutions().into_iter().find(|solution| solution.day == day)
+}
+
+pub fn input_path(day: u32) -> PathBuf {
+    Path::new("data").join(format!("day{:02}-input.txt", day))
+}
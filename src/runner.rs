@@ -0,0 +1,218 @@
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::registry::{find_solution, input_path, Solution};
+use crate::timing::{format_duration, nanos, serialize_nanos, Stats};
+
+// One part of a day run `runs` times. Loading the input is timed once, parsing
+// and solving on every run. The input is identified by its SHA-256 so results
+// from different machines can be matched up.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RunReport {
+    pub day: u32,
+    pub part: u32,
+    pub answer: Answer,
+    pub answer_type: String,
+    pub input: PathBuf,
+    pub input_hash: String,
+    pub runs: usize,
+    #[serde(rename = "load_ns", serialize_with = "serialize_nanos")]
+    pub load: Duration,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+#[derive(Debug)]
+pub enum RunError {
+    NoSolution(u32),
+    NoPart { day: u32, part: u32 },
+    Io { file: PathBuf, err: std::io::Error },
+    Failed { day: u32, part: u32, err: AocError },
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RunError::NoSolution(day) => write!(f, "no solution registered for day {}", day),
+            RunError::NoPart { day, part } => write!(f, "day {} has no part {}", day, part),
+            RunError::Io { file, err } => write!(f, "{}: {}", file.display(), err),
+            RunError::Failed { day, part, err } => write!(f, "day {} part {} failed: {}", day, part, err),
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+impl RunReport {
+    // Timings for a single line of output, e.g. "load 41.200 µs, parse 97.877 µs, solve 27.519 µs"
+    pub fn duration_summary(&self) -> String {
+        let summary = format!("load {}, parse {}, solve {}",
+            format_duration(self.load), format_duration(self.parse.median), format_duration(self.solve.median));
+        if self.runs == 1 {
+            summary
+        } else {
+            format!("{} (median of {} runs)", summary, self.runs)
+        }
+    }
+
+    pub fn text(&self) -> String {
+        let mut lines = vec![
+            format!("Day {} part {}: {}", self.day, self.part, self.answer),
+            format!("  {:<5} {:>12}", "load", format_duration(self.load)),
+        ];
+        for (phase, stats) in [("parse", &self.parse), ("solve", &self.solve)] {
+            if self.runs == 1 {
+                lines.push(format!("  {:<5} {:>12}", phase, format_duration(stats.median)));
+            } else {
+                lines.push(format!(
+                    "  {:<5} {:>12} median, {:>12} min, {:>12} p95",
+                    phase, format_duration(stats.median), format_duration(stats.min), format_duration(stats.p95)
+                ));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+// Reads the day's input and runs one of its parts
+pub fn run_day(day: u32, part: u32, runs: usize) -> Result<RunReport, RunError> {
+    let solution = find_solution(day).ok_or(RunError::NoSolution(day))?;
+    run_solution(&solution, part, runs)
+}
+
+pub fn run_solution(solution: &Solution, part: u32, runs: usize) -> Result<RunReport, RunError> {
+    let file = input_path(solution.day);
+    let start = Instant::now();
+    let input = fs::read_to_string(&file).map_err(|err| RunError::Io { file, err })?;
+    let load = start.elapsed();
+
+    let mut report = time_part(solution, &input, part, runs)?;
+    report.input = input_path(solution.day);
+    report.load = load;
+    Ok(report)
+}
+
+// Runs a part on an input that is already loaded, keeping the first answer.
+// Stops at the first run that fails.
+pub fn time_part(solution: &Solution, input: &str, part: u32, runs: usize) -> Result<RunReport, RunError> {
+    let mut answer: Option<Answer> = None;
+    let mut parse: Vec<Duration> = Vec::new();
+    let mut solve: Vec<Duration> = Vec::new();
+
+    for _ in 0..runs.max(1) {
+        let run = solution.run_timed(input, part).ok_or(RunError::NoPart { day: solution.day, part })?;
+        let run_answer = run.answer.map_err(|err| RunError::Failed { day: solution.day, part, err })?;
+        answer.get_or_insert(run_answer);
+        parse.push(run.parse);
+        solve.push(run.solve);
+    }
+
+    let answer = answer.expect("Parts should run at least once.");
+    Ok(RunReport {
+        day: solution.day,
+        part,
+        answer_type: answer.type_name().to_string(),
+        answer,
+        input: PathBuf::new(),
+        input_hash: input_hash(input),
+        runs: parse.len(),
+        load: Duration::ZERO,
+        parse: Stats::from_samples(&parse).expect("Parts should run at least once."),
+        solve: Stats::from_samples(&solve).expect("Parts should run at least once."),
+    })
+}
+
+pub fn input_hash(input: &str) -> String {
+    Sha256::digest(input.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+pub fn reports_json(reports: &[RunReport]) -> String {
+    serde_json::to_string_pretty(reports).expect("Reports should serialize.")
+}
+
+// One row per part with the timing stats flattened into columns
+pub fn reports_csv(reports: &[RunReport]) -> String {
+    let mut lines: Vec<String> = vec![[
+        "day", "part", "answer", "answer_type", "input", "input_hash", "runs", "load_ns",
+        "parse_min_ns", "parse_median_ns", "parse_p95_ns", "solve_min_ns", "solve_median_ns", "solve_p95_ns",
+    ].join(",")];
+    for r in reports.iter() {
+        let fields: Vec<String> = vec![
+            r.day.to_string(),
+            r.part.to_string(),
+            csv_field(&r.answer.to_string()),
+            csv_field(&r.answer_type),
+            csv_field(&r.input.display().to_string()),
+            r.input_hash.clone(),
+            r.runs.to_string(),
+            nanos(r.load).to_string(),
+            nanos(r.parse.min).to_string(),
+            nanos(r.parse.median).to_string(),
+            nanos(r.parse.p95).to_string(),
+            nanos(r.solve.min).to_string(),
+            nanos(r.solve.median).to_string(),
+            nanos(r.solve.p95).to_string(),
+        ];
+        lines.push(fields.join(","));
+    }
+    lines.join("\n")
+}
+
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::example_input;
+
+    #[test]
+    fn test_time_part() {
+        let solution = find_solution(6).unwrap();
+        let report = time_part(&solution, &example_input(6, "races"), 1, 5).unwrap();
+        assert_eq!(report.answer, Answer::from(288u64));
+        assert_eq!(report.runs, 5);
+        assert_eq!(report.parse.samples, 5);
+        assert!(report.solve.min <= report.solve.median && report.solve.median <= report.solve.p95);
+        assert!(report.text().starts_with("Day 6 part 1: 288\n  load "));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["answer"], "288");
+        assert_eq!(json["solve"]["samples"], 5);
+        assert!(json["load_ns"].is_u64());
+        assert_eq!(json["answer_type"], "u64");
+
+        assert!(matches!(time_part(&solution, "", 3, 1), Err(RunError::NoPart { day: 6, part: 3 })));
+    }
+
+    #[test]
+    fn test_reports_csv() {
+        let solution = find_solution(9).unwrap();
+        let mut report = time_part(&solution, &example_input(9, "histories"), 2, 1).unwrap();
+        report.input = PathBuf::from("inputs/a,b.txt");
+
+        let csv = reports_csv(&[report.clone()]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("day,part,answer,answer_type,input,input_hash,runs,load_ns,"));
+        assert!(lines[1].starts_with(format!("9,2,2,i64,\"inputs/a,b.txt\",{},1,", report.input_hash).as_str()));
+        assert_eq!(lines[1].split(',').count(), 15);
+    }
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!(input_hash("abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+}
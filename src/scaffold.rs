@@ -0,0 +1,224 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DAY_TEMPLATE: &str = include_str!("../templates/day.rs.tmpl");
+const BIN_TEMPLATE: &str = include_str!("../templates/bin.rs.tmpl");
+const BENCH_TEMPLATE: &str = include_str!("../templates/bench.rs.tmpl");
+const EXAMPLES_TEMPLATE: &str = include_str!("../templates/examples.toml.tmpl");
+
+// Adds the new day to an existing file, or names what it could not find
+type Patch = fn(&str, u32) -> Result<String, String>;
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    InvalidDay(u32),
+    Exists(PathBuf),
+    AlreadyRegistered { file: PathBuf, module: String },
+    MissingAnchor { file: PathBuf, anchor: String },
+    Io { file: PathBuf, err: std::io::Error },
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScaffoldError::InvalidDay(day) => write!(f, "day {} is not between 1 and 25", day),
+            ScaffoldError::Exists(file) => write!(f, "{} already exists", file.display()),
+            ScaffoldError::AlreadyRegistered { file, module } => {
+                write!(f, "{} already registers {}", file.display(), module)
+            },
+            ScaffoldError::MissingAnchor { file, anchor } => {
+                write!(f, "{}: could not find {}", file.display(), anchor)
+            },
+            ScaffoldError::Io { file, err } => write!(f, "{}: {}", file.display(), err),
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+// Creates the module, binary, bench and examples file for a new day from the
+// templates, then registers it in lib.rs, Cargo.toml, the solution registry and
+// the example list. Nothing is written unless every step can be done, and
+// existing files are never overwritten.
+pub fn scaffold_day(root: &Path, day: u32) -> Result<Vec<PathBuf>, ScaffoldError> {
+    if !(1..=25).contains(&day) {
+        return Err(ScaffoldError::InvalidDay(day));
+    }
+    let module = format!("day{:02}", day);
+
+    let created: Vec<(PathBuf, String)> = vec![
+        (Path::new("src").join(format!("{}.rs", module)), render(DAY_TEMPLATE, day)),
+        (Path::new("src").join("bin").join(format!("{}.rs", module)), render(BIN_TEMPLATE, day)),
+        (Path::new("benches").join(format!("{}.rs", module)), render(BENCH_TEMPLATE, day)),
+        (Path::new("data").join("examples").join(format!("{}.toml", module)), render(EXAMPLES_TEMPLATE, day)),
+    ];
+    for (file, _) in created.iter() {
+        if root.join(file).exists() {
+            return Err(ScaffoldError::Exists(file.clone()));
+        }
+    }
+
+    let patches: [(PathBuf, Patch); 4] = [
+        (PathBuf::from("Cargo.toml"), register_bench),
+        (Path::new("src").join("lib.rs"), register_module),
+        (Path::new("src").join("registry.rs"), register_solution),
+        (Path::new("src").join("examples.rs"), register_examples),
+    ];
+    let mut updated: Vec<(PathBuf, String)> = Vec::new();
+    for (file, patch) in patches.iter() {
+        let content = read(root, file)?;
+        if content.contains(registration(file, &module).as_str()) {
+            return Err(ScaffoldError::AlreadyRegistered { file: file.clone(), module });
+        }
+        let patched = patch(&content, day)
+            .map_err(|anchor| ScaffoldError::MissingAnchor { file: file.clone(), anchor })?;
+        updated.push((file.clone(), patched));
+    }
+
+    for (file, content) in created.iter().chain(updated.iter()) {
+        let path = root.join(file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| ScaffoldError::Io { file: file.clone(), err })?;
+        }
+        fs::write(&path, content).map_err(|err| ScaffoldError::Io { file: file.clone(), err })?;
+    }
+
+    Ok(created.into_iter().chain(updated).map(|(file, _)| file).collect())
+}
+
+fn render(template: &str, day: u32) -> String {
+    template
+        .replace("{{module}}", format!("day{:02}", day).as_str())
+        .replace("{{day}}", day.to_string().as_str())
+}
+
+fn read(root: &Path, file: &Path) -> Result<String, ScaffoldError> {
+    fs::read_to_string(root.join(file)).map_err(|err| ScaffoldError::Io { file: file.to_path_buf(), err })
+}
+
+// The text each file contains once the day is registered there
+fn registration(file: &Path, module: &str) -> String {
+    match file.file_name().and_then(|name| name.to_str()) {
+        Some("Cargo.toml") => format!("name = \"{}\"", module),
+        Some("lib.rs") => format!("pub mod {};", module),
+        Some("registry.rs") => format!("{}::", module),
+        _ => format!("examples/{}.toml", module),
+    }
+}
+
+fn register_bench(content: &str, day: u32) -> Result<String, String> {
+    let mut content = content.trim_end().to_string();
+    content.push_str(format!("\n\n[[bench]]\nname = \"day{:02}\"\nharness = false\n", day).as_str());
+    Ok(content)
+}
+
+// Keeps the day modules sorted, the other modules stay where they are
+fn register_module(content: &str, day: u32) -> Result<String, String> {
+    let module = format!("day{:02}", day);
+    let mut lines: Vec<String> = content.lines().map(|line| line.to_string()).collect();
+    let days: Vec<usize> = lines.iter().enumerate()
+        .filter(|(_, line)| line.starts_with("pub mod day"))
+        .map(|(i, _)| i)
+        .collect();
+    let last = *days.last().ok_or("pub mod dayNN;")?;
+    let position = days.iter()
+        .find(|i| lines[**i].as_str() > format!("pub mod {};", module).as_str())
+        .copied()
+        .unwrap_or(last + 1);
+
+    lines.insert(position, format!("pub mod {};", module));
+    Ok(lines.join("\n") + "\n")
+}
+
+fn register_solution(content: &str, day: u32) -> Result<String, String> {
+    let module = format!("day{:02}", day);
+
+    // Add the module to the imported days
+    let imports_start = content.find("use crate::{").ok_or("use crate::{...}")? + "use crate::{".len();
+    let imports_end = imports_start + content[imports_start..].find('}').ok_or("use crate::{...}")?;
+    let mut imports: Vec<&str> = content[imports_start..imports_end].split(',').map(|m| m.trim()).collect();
+    imports.push(module.as_str());
+    imports.sort();
+    let content = format!("{}{}{}", &content[..imports_start], imports.join(", "), &content[imports_end..]);
+
+    // And its solution at the end of the list
+    let list = content.find("pub fn solutions()").ok_or("pub fn solutions()")?;
+    let end = list + content[list..].find("\n    ]\n").ok_or("end of the solutions list")?;
+    let entry = format!(
+        "\n        solution!({}, {}::parse_input, {}::solve_part1, {}::solve_part2),",
+        day, module, module, module,
+    );
+    Ok(format!("{}{}{}", &content[..end], entry, &content[end..]))
+}
+
+fn register_examples(content: &str, day: u32) -> Result<String, String> {
+    let list = content.find("const EXAMPLE_FILES").ok_or("const EXAMPLE_FILES")?;
+    let end = list + content[list..].find("\n];").ok_or("end of EXAMPLE_FILES")?;
+    let entry = format!("\n    ({}, include_str!(\"../data/examples/day{:02}.toml\")),", day, day);
+    Ok(format!("{}{}{}", &content[..end], entry, &content[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::parse_examples;
+
+    fn create_project(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("aoc2023-scaffold-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for file in ["Cargo.toml", "src/lib.rs", "src/registry.rs", "src/examples.rs", "src/day13.rs"] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::copy(file, path).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn test_scaffold_day() {
+        let root = create_project("new");
+        let files = scaffold_day(&root, 14).unwrap();
+        assert_eq!(files.len(), 8);
+
+        let module = fs::read_to_string(root.join("src/day14.rs")).unwrap();
+        assert!(module.contains("crate::example_tests!(14);"));
+        let bin = fs::read_to_string(root.join("src/bin/day14.rs")).unwrap();
+        assert!(bin.contains("run_day(14, 2, 1)"));
+
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("pub mod day13;\npub mod day14;\n"));
+        let cargo = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert!(cargo.ends_with("[[bench]]\nname = \"day14\"\nharness = false\n"));
+        let registry = fs::read_to_string(root.join("src/registry.rs")).unwrap();
+        assert!(registry.contains("day12, day13, day14};"));
+        assert!(registry.contains("solution!(14, day14::parse_input, day14::solve_part1, day14::solve_part2),\n    ]"));
+        let examples = fs::read_to_string(root.join("src/examples.rs")).unwrap();
+        assert!(examples.contains("(14, include_str!(\"../data/examples/day14.toml\")),\n];"));
+
+        // A placeholder example keeps the generated example test passing
+        let example_file = fs::read_to_string(root.join("data/examples/day14.toml")).unwrap();
+        let examples = parse_examples(14, &example_file).unwrap();
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].answers.get(&1).map(String::as_str), Some("0"));
+
+        // A second run must not touch anything
+        assert!(matches!(scaffold_day(&root, 14), Err(ScaffoldError::Exists(_))));
+        assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(), lib);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_scaffold_refuses_existing_day() {
+        let root = create_project("existing");
+        assert!(matches!(scaffold_day(&root, 13), Err(ScaffoldError::Exists(_))));
+        assert!(matches!(scaffold_day(&root, 26), Err(ScaffoldError::InvalidDay(26))));
+
+        // Registered without the files, e.g. after deleting the module by hand
+        assert!(matches!(scaffold_day(&root, 12), Err(ScaffoldError::AlreadyRegistered { .. })));
+        assert!(!root.join("src/day12.rs").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+// Summary of repeated timings of the same run, serialized in nanoseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub samples: usize,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        Some(Stats {
+            min: sorted[0],
+            median: percentile(&sorted, 50),
+            p95: percentile(&sorted, 95),
+            samples: sorted.len(),
+        })
+    }
+}
+
+impl Serialize for Stats {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut stats = serializer.serialize_struct("Stats", 4)?;
+        stats.serialize_field("min_ns", &nanos(self.min))?;
+        stats.serialize_field("median_ns", &nanos(self.median))?;
+        stats.serialize_field("p95_ns", &nanos(self.p95))?;
+        stats.serialize_field("samples", &self.samples)?;
+        stats.end()
+    }
+}
+
+pub fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos() as u64
+}
+
+pub fn serialize_nanos<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(nanos(*duration))
+}
+
+pub fn serialize_optional_nanos<S: Serializer>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+    duration.map(nanos).serialize(serializer)
+}
+
+// Picks the unit so that short runs do not show up as 0 ms
+pub fn format_duration(duration: Duration) -> String {
+    let ns = duration.as_nanos();
+    if ns < 1_000 {
+        format!("{} ns", ns)
+    } else if ns < 1_000_000 {
+        format!("{:.3} µs", ns as f64 / 1e3)
+    } else if ns < 1_000_000_000 {
+        format!("{:.3} ms", ns as f64 / 1e6)
+    } else {
+        format!("{:.3} s", ns as f64 / 1e9)
+    }
+}
+
+// Nearest-rank percentile of sorted samples
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    let rank = (sorted.len() * percent).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<Duration> = (1..=20).rev().map(Duration::from_nanos).collect();
+        let stats = Stats::from_samples(&samples).unwrap();
+        assert_eq!(stats.min, Duration::from_nanos(1));
+        assert_eq!(stats.median, Duration::from_nanos(10));
+        assert_eq!(stats.p95, Duration::from_nanos(19));
+        assert_eq!(stats.samples, 20);
+
+        let single = Stats::from_samples(&[Duration::from_nanos(7)]).unwrap();
+        assert_eq!((single.min, single.median, single.p95), (Duration::from_nanos(7), Duration::from_nanos(7), Duration::from_nanos(7)));
+        assert_eq!(Stats::from_samples(&[]), None);
+
+        let json = serde_json::to_value(stats).unwrap();
+        assert_eq!(json, serde_json::json!({ "min_ns": 1, "median_ns": 10, "p95_ns": 19, "samples": 20 }));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999 ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.345 µs");
+        assert_eq!(format_duration(Duration::from_micros(1_500)), "1.500 ms");
+        assert_eq!(format_duration(Duration::from_millis(2_250)), "2.250 s");
+    }
+}
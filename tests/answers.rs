@@ -0,0 +1,13 @@
+use std::path::Path;
+
+use aoc2023::answers::{summary_table, verify, Answers};
+use aoc2023::registry::solutions;
+
+#[test]
+fn test_recorded_answers() {
+    let answers = Answers::load(Path::new("data").join("answers.toml").as_path()).unwrap();
+    let verifications = verify(&solutions(), &answers);
+
+    let failures = verifications.iter().filter(|v| v.is_failure()).count();
+    assert_eq!(failures, 0, "\n{}", summary_table(&verifications));
+}
@@ -0,0 +1,53 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use aoc2023::scaffold::scaffold_day;
+
+fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).unwrap() {
+        let path = entry.unwrap().path();
+        let target = to.join(path.file_name().unwrap());
+        if path.is_dir() {
+            copy_dir(&path, &target);
+        } else {
+            fs::copy(&path, &target).unwrap();
+        }
+    }
+}
+
+// Scaffolds a day into a copy of the project and runs the generated tests, so
+// the templates are known to compile and pass before anyone fills them in.
+// The copy builds into its own directory under target/ so that later runs
+// only rebuild the crate itself.
+#[test]
+fn test_scaffolded_day_builds_and_passes() {
+    let root = std::env::temp_dir().join(format!("aoc2023-scaffold-build-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    for dir in ["src", "benches", "templates", "data"] {
+        copy_dir(Path::new(dir), &root.join(dir));
+    }
+    for file in ["Cargo.toml", "Cargo.lock"] {
+        if Path::new(file).exists() {
+            fs::copy(file, root.join(file)).unwrap();
+        }
+    }
+
+    scaffold_day(&root, 14).unwrap();
+
+    let target_dir: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR")).join("target").join("scaffold-check");
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .args(["test", "--offline", "--lib", "day14"])
+        .current_dir(&root)
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}\n{}", stdout, String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("day14::tests::test_examples ... ok"), "{}", stdout);
+
+    fs::remove_dir_all(&root).unwrap();
+}